@@ -0,0 +1,53 @@
+//! Compares batched transforms with transforming each vector separately
+//!
+//! Run with `cargo bench --bench batch`. For each vector length the benchmark
+//! reports the best wall-clock time over a few repetitions for a batch of
+//! `f32` vectors totalling 2^16 elements, small enough to stay in cache.
+
+use fwht::batch::fwht_batch;
+use fwht::core::fwht_slice;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const REPETITIONS: usize = 200;
+
+fn best_time<F: FnMut(&mut [f32])>(input: &[f32], mut transform: F) -> Duration {
+    let mut data = input.to_vec();
+    (0..REPETITIONS)
+        .map(|_| {
+            data.copy_from_slice(input);
+            let start = Instant::now();
+            transform(black_box(&mut data[..]));
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    println!(
+        "{:>6} {:>8} {:>12} {:>12} {:>8}",
+        "n", "batch", "per vector", "batched", "speedup"
+    );
+
+    for log2 in [2, 4, 6, 8, 10] {
+        let n = 1usize << log2;
+        let input: Vec<f32> = (0..1 << 16).map(|i| (i % 1021) as f32).collect();
+
+        let per_vector = best_time(&input, |data| {
+            for row in data.chunks_exact_mut(n) {
+                fwht_slice(row).unwrap();
+            }
+        });
+        let batched = best_time(&input, |data| fwht_batch(data, n).unwrap());
+
+        println!(
+            "{:>6} {:>8} {:>12.2?} {:>12.2?} {:>7.2}x",
+            n,
+            input.len() / n,
+            per_vector,
+            batched,
+            per_vector.as_secs_f64() / batched.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,57 @@
+//! Compares the radix-2, radix-4, radix-8 and cache-blocked kernels
+//!
+//! Run with `cargo bench --bench kernels`. For each length the benchmark
+//! reports the number of full passes over the data each kernel makes and
+//! the best wall-clock time over a few repetitions. The last column is the
+//! SIMD kernel of the widest backend the CPU supports.
+
+use fwht::core::{fwht_slice_with_kernel, Kernel};
+use fwht::simd::{detected_backend, fwht_simd_with};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const REPETITIONS: usize = 20;
+
+const KERNELS: [(&str, Kernel); 4] = [
+    ("radix2", Kernel::Radix2),
+    ("radix4", Kernel::Radix4),
+    ("radix8", Kernel::Radix8),
+    ("blocked", Kernel::Blocked),
+];
+
+fn best_time<F: Fn(&mut [f64])>(input: &[f64], transform: F) -> Duration {
+    let mut data = input.to_vec();
+    (0..REPETITIONS)
+        .map(|_| {
+            // Start every repetition from the same values so that repeated
+            // transforms never overflow to infinities
+            data.copy_from_slice(input);
+            let start = Instant::now();
+            transform(black_box(&mut data[..]));
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    print!("{:>6}", "log2 n");
+    for (name, _) in KERNELS {
+        print!(" {:>6} {:>12}", "passes", name);
+    }
+    let backend = detected_backend();
+    println!(" {:>12}", format!("simd {}", backend));
+
+    for log2 in [10, 13, 16, 18, 20, 22, 24] {
+        let n = 1usize << log2;
+        let input: Vec<f64> = (0..n).map(|i| (i % 1021) as f64).collect();
+
+        print!("{:>6}", log2);
+        for (_, kernel) in KERNELS {
+            let time = best_time(&input, |data| fwht_slice_with_kernel(data, kernel).unwrap());
+            print!(" {:>6} {:>12.2?}", kernel.memory_passes::<f64>(n), time);
+        }
+        let time = best_time(&input, |data| fwht_simd_with(data, backend).unwrap());
+        println!(" {:>12.2?}", time);
+    }
+}
@@ -0,0 +1,353 @@
+//! Batched transforms over many equal-length vectors
+//!
+//! Transforming thousands of short vectors one at a time spends most of the
+//! time in the narrow butterfly stages, which combine neighbouring elements
+//! of one vector and do not vectorize. For short vectors these functions
+//! instead interleave [`batch_lanes`] vectors at a time, element by element,
+//! into a scratch tile that fits in the L1 cache, so that every butterfly
+//! works on runs of contiguous elements from different vectors and
+//! vectorizes across the batch. Longer vectors are transformed one by one
+//! with the usual kernels.
+//!
+//! Every vector goes through the same butterflies as with
+//! [`fwht_slice`](crate::core::fwht_slice), so the results are bit-identical.
+//!
+//! # Examples
+//!
+//! ```
+//! use fwht::batch::{fwht_batch, fwht_batch_vecs};
+//!
+//! // Three vectors of length 4, stored back to back
+//! let mut data = [1.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 2.0, 2.0, 2.0, 2.0];
+//! fwht_batch(&mut data, 4).unwrap();
+//! assert_eq!(data, [3.0, 1.0, 1.0, -1.0, 1.0, 1.0, 1.0, 1.0, 8.0, 0.0, 0.0, 0.0]);
+//!
+//! let mut rows = vec![vec![1, 1, 1, 0], vec![1, 0, 0, 0]];
+//! fwht_batch_vecs(&mut rows).unwrap();
+//! assert_eq!(rows, vec![vec![3, 1, 1, -1], vec![1, 1, 1, 1]]);
+//! ```
+
+use crate::core::{fwht_unchecked, interleaved_stages, validate_length, validate_multiple};
+use crate::error::FwhtError;
+use std::ops::{Add, Sub};
+
+/// Largest run of interleaved elements, in bytes
+pub const BATCH_LANE_BYTES: usize = 256;
+
+/// Smallest run worth interleaving, in bytes
+///
+/// Below this, the cost of interleaving outweighs the faster butterflies.
+pub const BATCH_MIN_LANE_BYTES: usize = 128;
+
+/// Size in bytes of the scratch tile of interleaved vectors
+///
+/// Sized to stay in the L1 cache. Longer vectors interleave fewer at a time,
+/// and vectors too long to fill [`BATCH_MIN_LANE_BYTES`] runs are transformed
+/// one by one, since their wide stages already vectorize.
+pub const BATCH_TILE_BYTES: usize = 1 << 15;
+
+/// Number of vectors of `n` elements of type `T` interleaved per tile
+///
+/// Returns 1 when vectors of this length are transformed one by one.
+///
+/// ```
+/// use fwht::batch::batch_lanes;
+///
+/// assert_eq!(batch_lanes::<f32>(16), 64);
+/// assert_eq!(batch_lanes::<f32>(256), 32);
+/// assert_eq!(batch_lanes::<f64>(256), 16);
+/// assert_eq!(batch_lanes::<f64>(1 << 12), 1);
+/// ```
+pub fn batch_lanes<T>(n: usize) -> usize {
+    let size = std::mem::size_of::<T>().max(1);
+    let lanes = (BATCH_TILE_BYTES / n.saturating_mul(size).max(1)).min(BATCH_LANE_BYTES / size);
+    if n > 1 && lanes * size >= BATCH_MIN_LANE_BYTES {
+        lanes
+    } else {
+        1
+    }
+}
+
+/// Transforms every consecutive run of `n` elements of `data` in place
+///
+/// `data` holds `data.len() / n` vectors back to back (row-major
+/// `batch × n`).
+///
+/// # Errors
+///
+/// - [`FwhtError::NotPowerOfTwo`] if `n` is not a power of 2
+/// - [`FwhtError::LengthMismatch`] if `data.len()` is not a multiple of `n`,
+///   with the next multiple as `expected`
+pub fn fwht_batch<T>(data: &mut [T], n: usize) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    validate_batch(data.len(), n)?;
+    batch_unchecked(data, n, &mut Vec::new());
+    Ok(())
+}
+
+/// Transforms every vector of `rows` in place
+///
+/// # Errors
+///
+/// - [`FwhtError::LengthMismatch`] if the vectors do not all have the length
+///   of the first one
+/// - [`FwhtError::NotPowerOfTwo`] if that length is not a power of 2
+pub fn fwht_batch_vecs<T>(rows: &mut [Vec<T>]) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    let n = validate_rows(rows)?;
+    vecs_unchecked(rows, n, &mut Vec::new());
+    Ok(())
+}
+
+/// Checks the batch layout of `len` elements in vectors of `n`
+fn validate_batch(len: usize, n: usize) -> Result<(), FwhtError> {
+    validate_length(n)?;
+    validate_multiple(len, n)
+}
+
+/// Checks that all rows share a valid length and returns it
+fn validate_rows<T>(rows: &[Vec<T>]) -> Result<usize, FwhtError> {
+    let n = rows.first().map_or(0, Vec::len);
+    if let Some(row) = rows.iter().find(|row| row.len() != n) {
+        return Err(FwhtError::LengthMismatch {
+            expected: n,
+            got: row.len(),
+        });
+    }
+    validate_length(n)?;
+    Ok(n)
+}
+
+/// Batched transform of a validated contiguous batch, using `tile` as scratch
+fn batch_unchecked<T>(data: &mut [T], n: usize, tile: &mut Vec<T>)
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    let lanes = batch_lanes::<T>(n);
+    if lanes == 1 {
+        for row in data.chunks_exact_mut(n.max(1)) {
+            fwht_unchecked(row);
+        }
+        return;
+    }
+
+    let mut groups = data.chunks_exact_mut(n * lanes);
+    for group in &mut groups {
+        let rows: &[T] = group;
+        tile.clear();
+        tile.extend((0..n).flat_map(|i| (0..lanes).map(move |b| rows[b * n + i])));
+        interleaved_stages(tile, lanes);
+        for (b, row) in group.chunks_exact_mut(n).enumerate() {
+            for (i, x) in row.iter_mut().enumerate() {
+                *x = tile[i * lanes + b];
+            }
+        }
+    }
+    for row in groups.into_remainder().chunks_exact_mut(n) {
+        fwht_unchecked(row);
+    }
+}
+
+/// Batched transform of validated rows, using `tile` as scratch
+fn vecs_unchecked<T>(rows: &mut [Vec<T>], n: usize, tile: &mut Vec<T>)
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    let lanes = batch_lanes::<T>(n);
+    if lanes == 1 {
+        for row in rows.iter_mut() {
+            fwht_unchecked(row);
+        }
+        return;
+    }
+
+    let mut groups = rows.chunks_exact_mut(lanes);
+    for group in &mut groups {
+        tile.clear();
+        tile.extend((0..n).flat_map(|i| group.iter().map(move |row| row[i])));
+        interleaved_stages(tile, lanes);
+        for (i, run) in tile.chunks_exact(lanes).enumerate() {
+            for (row, &x) in group.iter_mut().zip(run) {
+                row[i] = x;
+            }
+        }
+    }
+    for row in groups.into_remainder() {
+        fwht_unchecked(row);
+    }
+}
+
+/// Number of tiles handed to each rayon task
+#[cfg(feature = "rayon")]
+const PARALLEL_TILES: usize = 16;
+
+/// Parallel version of [`fwht_batch`] that splits the batch across threads
+///
+/// Produces exactly the same result as [`fwht_batch`].
+///
+/// # Errors
+///
+/// Same as [`fwht_batch`].
+///
+/// # Examples
+///
+/// ```
+/// use fwht::batch::fwht_batch_par;
+///
+/// let mut data = vec![1.0f32; 10_000 * 256];
+/// fwht_batch_par(&mut data, 256).unwrap();
+/// assert!(data.chunks(256).all(|row| row[0] == 256.0 && row[1] == 0.0));
+/// ```
+#[cfg(feature = "rayon")]
+pub fn fwht_batch_par<T>(data: &mut [T], n: usize) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Send + Sync,
+{
+    use rayon::prelude::*;
+
+    validate_batch(data.len(), n)?;
+    if n == 0 {
+        return Ok(());
+    }
+
+    let rows = batch_lanes::<T>(n) * PARALLEL_TILES;
+    data.par_chunks_mut(n * rows)
+        .for_each_init(Vec::new, |tile, chunk| batch_unchecked(chunk, n, tile));
+    Ok(())
+}
+
+/// Parallel version of [`fwht_batch_vecs`] that splits the rows across threads
+///
+/// Produces exactly the same result as [`fwht_batch_vecs`].
+///
+/// # Errors
+///
+/// Same as [`fwht_batch_vecs`].
+#[cfg(feature = "rayon")]
+pub fn fwht_batch_vecs_par<T>(rows: &mut [Vec<T>]) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Send + Sync,
+{
+    use rayon::prelude::*;
+
+    let n = validate_rows(rows)?;
+    let chunk = batch_lanes::<T>(n) * PARALLEL_TILES;
+    rows.par_chunks_mut(chunk)
+        .for_each_init(Vec::new, |tile, chunk| vecs_unchecked(chunk, n, tile));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::fwht_slice;
+
+    fn rows(batch: usize, n: usize) -> Vec<Vec<f64>> {
+        (0..batch)
+            .map(|b| {
+                (0..n)
+                    .map(|i| ((i * 2654435761 + b * 40503) % 1000) as f64 * 1e-3 - 0.5)
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn expected(rows: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        rows.iter()
+            .map(|row| {
+                let mut row = row.clone();
+                fwht_slice(&mut row).unwrap();
+                row
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_batch_bit_identical_to_rows() {
+        // Full tiles, a partial tile, and lengths on both sides of the
+        // interleaving threshold
+        for (batch, n) in [(70, 1), (70, 2), (100, 16), (33, 256), (5, 1 << 12)] {
+            let input = rows(batch, n);
+            let expected = expected(&input);
+
+            let mut flat: Vec<f64> = input.concat();
+            fwht_batch(&mut flat, n).unwrap();
+            assert_eq!(flat, expected.concat(), "batch {} n {}", batch, n);
+
+            let mut vecs = input;
+            fwht_batch_vecs(&mut vecs).unwrap();
+            assert_eq!(vecs, expected, "batch {} n {}", batch, n);
+        }
+    }
+
+    #[test]
+    fn test_batch_integers() {
+        let mut data: Vec<i32> = (0..200 * 8).map(|i| i % 5 - 2).collect();
+        let mut expected = data.clone();
+        for row in expected.chunks_exact_mut(8) {
+            fwht_slice(row).unwrap();
+        }
+
+        fwht_batch(&mut data, 8).unwrap();
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn test_batch_errors_and_edge_cases() {
+        let mut data = [1.0; 12];
+        assert_eq!(
+            fwht_batch(&mut data, 3),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+        assert_eq!(
+            fwht_batch(&mut data, 8),
+            Err(FwhtError::LengthMismatch {
+                expected: 16,
+                got: 12
+            })
+        );
+
+        let mut empty: [f64; 0] = [];
+        fwht_batch(&mut empty, 0).unwrap();
+        fwht_batch(&mut empty, 4).unwrap();
+
+        let mut ragged = vec![vec![1.0; 4], vec![1.0; 4], vec![1.0; 2]];
+        assert_eq!(
+            fwht_batch_vecs(&mut ragged),
+            Err(FwhtError::LengthMismatch {
+                expected: 4,
+                got: 2
+            })
+        );
+
+        let mut no_rows: Vec<Vec<f64>> = vec![];
+        fwht_batch_vecs(&mut no_rows).unwrap();
+
+        let mut bad = vec![vec![1.0; 3]];
+        assert_eq!(
+            fwht_batch_vecs(&mut bad),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_batch_par_matches_serial() {
+        for (batch, n) in [(3000, 16), (1000, 256), (20, 1 << 12)] {
+            let input = rows(batch, n);
+            let expected = expected(&input);
+
+            let mut flat: Vec<f64> = input.concat();
+            fwht_batch_par(&mut flat, n).unwrap();
+            assert_eq!(flat, expected.concat(), "batch {} n {}", batch, n);
+
+            let mut vecs = input;
+            fwht_batch_vecs_par(&mut vecs).unwrap();
+            assert_eq!(vecs, expected, "batch {} n {}", batch, n);
+        }
+    }
+}
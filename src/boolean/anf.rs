@@ -0,0 +1,307 @@
+//! Algebraic normal form over GF(2)
+//!
+//! Every Boolean function has a unique algebraic normal form (ANF): an XOR of
+//! monomials `x_S = Π xᵢ` over sets `S` of variables. The ANF coefficients
+//! are the binary Möbius transform of the truth table,
+//!
+//! ```text
+//! a[S] = ⊕ f[T]  over all T ⊆ S
+//! ```
+//!
+//! which runs on the butterfly loop of the Walsh-Hadamard transform with XOR
+//! as the butterfly. Over GF(2) the transform is its own inverse, so the same
+//! pass also maps an ANF back to its truth table.
+//!
+//! The stages inside a word run as six mask-and-shift steps on the whole
+//! word; the remaining stages XOR entire words, 64 entries at a time.
+//!
+//! # Examples
+//!
+//! ```
+//! use fwht::boolean::{algebraic_degree, anf_from_truth_table};
+//!
+//! // Majority of three variables: f(x) = 1 for x in {3, 5, 6, 7}
+//! let mut table = [0b1110_1000u64];
+//! assert_eq!(algebraic_degree(&table, 3).unwrap(), 2);
+//!
+//! // ANF: x0·x1 ⊕ x0·x2 ⊕ x1·x2, the monomials 3, 5 and 6
+//! anf_from_truth_table(&mut table, 3).unwrap();
+//! assert_eq!(table, [0b0110_1000]);
+//! ```
+
+use crate::core::pair_stages;
+use crate::error::FwhtError;
+
+/// Masks of the entries whose index has bit `j` clear, for `j < 6`
+const MOBIUS_MASKS: [u64; 6] = [
+    0x5555_5555_5555_5555,
+    0x3333_3333_3333_3333,
+    0x0f0f_0f0f_0f0f_0f0f,
+    0x00ff_00ff_00ff_00ff,
+    0x0000_ffff_0000_ffff,
+    0x0000_0000_ffff_ffff,
+];
+
+/// Masks of the bit positions `p < 64` with `popcount(p) = d`, for `d ≤ 6`
+const WEIGHT_MASKS: [u64; 7] = {
+    let mut masks = [0u64; 7];
+    let mut p = 0;
+    while p < 64 {
+        masks[(p as u64).count_ones() as usize] |= 1 << p;
+        p += 1;
+    }
+    masks
+};
+
+/// Converts a bit-packed truth table to its ANF coefficients in place
+///
+/// Bit `S` of the result is the coefficient of the monomial `x_S`. Tables of
+/// fewer than 6 variables use the low `2^n` bits of a single word; the
+/// remaining bits are ignored.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::LengthMismatch`] if `table` does not hold exactly
+/// `max(1, 2^n / 64)` words, and [`FwhtError::Overflow`] if `2^n` does not
+/// fit in `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::boolean::anf_from_truth_table;
+///
+/// // f(x0, x1) = x0 OR x1 = x0 ⊕ x1 ⊕ x0·x1
+/// let mut table = [0b1110u64];
+/// anf_from_truth_table(&mut table, 2).unwrap();
+/// assert_eq!(table, [0b1110]);
+///
+/// // f(x0, x1) = NOT x0 = 1 ⊕ x0
+/// let mut table = [0b0101u64];
+/// anf_from_truth_table(&mut table, 2).unwrap();
+/// assert_eq!(table, [0b0011]);
+/// ```
+pub fn anf_from_truth_table(table: &mut [u64], num_vars: u32) -> Result<(), FwhtError> {
+    validate_table(table.len(), num_vars)?;
+    binary_mobius(table, num_vars);
+    Ok(())
+}
+
+/// Converts ANF coefficients to the bit-packed truth table in place
+///
+/// The binary Möbius transform is an involution, so this is the same pass as
+/// [`anf_from_truth_table`].
+///
+/// # Errors
+///
+/// Same as [`anf_from_truth_table`].
+///
+/// # Examples
+///
+/// ```
+/// use fwht::boolean::truth_table_from_anf;
+///
+/// // x0·x1 ⊕ x2
+/// let mut anf = [(1u64 << 0b011) | (1 << 0b100)];
+/// truth_table_from_anf(&mut anf, 3).unwrap();
+/// assert_eq!(anf, [0b0111_1000]);
+/// ```
+pub fn truth_table_from_anf(anf: &mut [u64], num_vars: u32) -> Result<(), FwhtError> {
+    anf_from_truth_table(anf, num_vars)
+}
+
+/// Algebraic degree of a Boolean function given by its truth table
+///
+/// The degree is the size of the largest monomial in the ANF. Constant
+/// functions, including the zero function, have degree 0. The table is
+/// copied, so this allocates one table's worth of words.
+///
+/// # Errors
+///
+/// Same as [`anf_from_truth_table`].
+///
+/// # Examples
+///
+/// ```
+/// use fwht::boolean::algebraic_degree;
+///
+/// assert_eq!(algebraic_degree(&[0b1000], 2).unwrap(), 2); // x0·x1
+/// assert_eq!(algebraic_degree(&[0b0110], 2).unwrap(), 1); // x0 ⊕ x1
+/// assert_eq!(algebraic_degree(&[0b1111], 2).unwrap(), 0); // 1
+/// ```
+pub fn algebraic_degree(table: &[u64], num_vars: u32) -> Result<u32, FwhtError> {
+    validate_table(table.len(), num_vars)?;
+    let mut anf = table.to_vec();
+    binary_mobius(&mut anf, num_vars);
+    Ok(anf_degree(&anf, num_vars))
+}
+
+/// Returns the number of words of a table of `num_vars` variables
+pub(crate) fn table_words(num_vars: u32) -> Result<usize, FwhtError> {
+    1usize
+        .checked_shl(num_vars)
+        .map(|entries| entries.div_ceil(64))
+        .ok_or(FwhtError::Overflow)
+}
+
+/// Returns the mask of the bits of a word that hold table entries
+pub(crate) fn valid_bits(num_vars: u32) -> u64 {
+    match num_vars {
+        0..=5 => (1 << (1 << num_vars)) - 1,
+        _ => u64::MAX,
+    }
+}
+
+/// Checks that a table of `len` words has `num_vars` variables
+fn validate_table(len: usize, num_vars: u32) -> Result<(), FwhtError> {
+    let expected = table_words(num_vars)?;
+    if len != expected {
+        return Err(FwhtError::LengthMismatch { expected, got: len });
+    }
+    Ok(())
+}
+
+/// Binary Möbius transform of a validated table
+pub(crate) fn binary_mobius(table: &mut [u64], num_vars: u32) {
+    for (j, mask) in MOBIUS_MASKS.iter().enumerate().take(num_vars as usize) {
+        for word in table.iter_mut() {
+            *word ^= (*word & mask) << (1 << j);
+        }
+    }
+    pair_stages(table, 1, table.len(), xor_lo_into_hi);
+}
+
+/// Degree of the ANF `anf`, ignoring bits outside the table
+pub(crate) fn anf_degree(anf: &[u64], num_vars: u32) -> u32 {
+    let valid = valid_bits(num_vars);
+    let mut degree = 0;
+    for (k, &word) in anf.iter().enumerate() {
+        let word = word & valid;
+        if word == 0 {
+            continue;
+        }
+        // Index k·64 + p has weight popcount(k) + popcount(p)
+        let within = (0..WEIGHT_MASKS.len() as u32)
+            .rev()
+            .find(|&d| word & WEIGHT_MASKS[d as usize] != 0)
+            .unwrap_or(0);
+        degree = degree.max(k.count_ones() + within);
+        if degree == num_vars {
+            break;
+        }
+    }
+    degree
+}
+
+/// Butterfly of the binary Möbius transform on whole words
+fn xor_lo_into_hi(lo: &mut [u64], hi: &mut [u64]) {
+    for (a, b) in lo.iter().zip(hi.iter_mut()) {
+        *b ^= *a;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bit(table: &[u64], x: usize) -> bool {
+        table[x / 64] >> (x % 64) & 1 == 1
+    }
+
+    fn naive_mobius(table: &[u64], num_vars: u32) -> Vec<u64> {
+        let n = 1usize << num_vars;
+        let mut result = vec![0u64; table.len()];
+        for s in 0..n {
+            let coefficient = (0..n)
+                .filter(|&t| t & s == t)
+                .filter(|&t| bit(table, t))
+                .count()
+                % 2;
+            result[s / 64] |= (coefficient as u64) << (s % 64);
+        }
+        result
+    }
+
+    fn sample(num_vars: u32) -> Vec<u64> {
+        let words = table_words(num_vars).unwrap();
+        (0..words as u64)
+            .map(|i| {
+                (i + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15).rotate_left(17) & valid_bits(num_vars)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_naive_mobius() {
+        for num_vars in 0..10 {
+            let table = sample(num_vars);
+            let mut anf = table.clone();
+            anf_from_truth_table(&mut anf, num_vars).unwrap();
+            assert_eq!(
+                anf,
+                naive_mobius(&table, num_vars),
+                "{} variables",
+                num_vars
+            );
+        }
+    }
+
+    #[test]
+    fn test_involution() {
+        for num_vars in [0, 3, 6, 7, 12] {
+            let table = sample(num_vars);
+            let mut data = table.clone();
+            anf_from_truth_table(&mut data, num_vars).unwrap();
+            truth_table_from_anf(&mut data, num_vars).unwrap();
+            assert_eq!(data, table);
+        }
+    }
+
+    #[test]
+    fn test_small_tables_ignore_unused_bits() {
+        let mut table = [0b1000u64 | (0xff << 32)];
+        anf_from_truth_table(&mut table, 2).unwrap();
+        assert_eq!(table[0] & 0b1111, 0b1000);
+        assert_eq!(algebraic_degree(&[0b1000 | (1 << 63)], 2).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_degree() {
+        // Product of all variables has full degree
+        for num_vars in [1, 5, 6, 7, 9] {
+            let mut table = vec![0u64; table_words(num_vars).unwrap()];
+            let last = (1usize << num_vars) - 1;
+            table[last / 64] |= 1 << (last % 64);
+            assert_eq!(algebraic_degree(&table, num_vars).unwrap(), num_vars);
+        }
+
+        // x7 ⊕ x0·x1 on 8 variables: degree 2, crossing words
+        let mut anf = vec![0u64; 4];
+        anf[128 / 64] |= 1;
+        anf[0] |= 1 << 3;
+        truth_table_from_anf(&mut anf, 8).unwrap();
+        assert_eq!(algebraic_degree(&anf, 8).unwrap(), 2);
+
+        assert_eq!(algebraic_degree(&[0, 0], 7).unwrap(), 0);
+        assert_eq!(algebraic_degree(&[u64::MAX; 2], 7).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut table = [0u64; 3];
+        assert_eq!(
+            anf_from_truth_table(&mut table, 8),
+            Err(FwhtError::LengthMismatch {
+                expected: 4,
+                got: 3
+            })
+        );
+        assert_eq!(
+            algebraic_degree(&table, 2),
+            Err(FwhtError::LengthMismatch {
+                expected: 1,
+                got: 3
+            })
+        );
+        assert_eq!(table_words(usize::BITS), Err(FwhtError::Overflow));
+    }
+}
@@ -0,0 +1,423 @@
+//! Cryptographic metrics read off the Walsh spectrum
+//!
+//! [`WalshMetrics`] holds one Walsh spectrum `W(a) = Σ (-1)^(f(x) ⊕ a·x)` and
+//! answers every question about linear structure from it: nonlinearity,
+//! linearity, bentness, balancedness, the best affine approximations, and
+//! the correlation immunity and resiliency orders.
+//!
+//! # Examples
+//!
+//! ```
+//! use fwht::boolean::TruthTable;
+//!
+//! // x0·x1 ⊕ x2·x3 is the smallest bent function
+//! let bent = TruthTable::from_fn(4, |x| (x & x >> 1 ^ x >> 2 & x >> 3) & 1 == 1).unwrap();
+//! let metrics = bent.metrics();
+//! assert_eq!(metrics.nonlinearity(), 6);
+//! assert_eq!(metrics.linearity(), 4);
+//! assert!(metrics.is_bent());
+//! assert!(!metrics.is_balanced());
+//! ```
+
+use crate::core::is_valid_fwht_length;
+use crate::error::FwhtError;
+
+/// Walsh spectrum of a Boolean function together with its metrics
+///
+/// Built by [`TruthTable::metrics`](super::TruthTable::metrics), or by
+/// [`WalshMetrics::from_spectrum`] from a spectrum computed elsewhere, e.g.
+/// with [`fwht_slice`](crate::fwht_slice) on the ±1 form of a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalshMetrics {
+    num_vars: u32,
+    spectrum: Vec<i64>,
+    linearity: u64,
+}
+
+/// An affine function `a·x ⊕ c` and how often it agrees with `f`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AffineApproximation {
+    /// Linear mask `a`, bit `i` selects the variable `xᵢ`
+    pub mask: usize,
+    /// Constant term `c`
+    pub constant: bool,
+    /// Number of inputs `x` with `f(x) = a·x ⊕ c`
+    pub agreements: u64,
+}
+
+impl WalshMetrics {
+    /// Wraps the Walsh spectrum of a function of `log2(spectrum.len())` variables
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::NotPowerOfTwo`] if `spectrum` is empty or its
+    /// length is not a power of 2.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::WalshMetrics;
+    /// use fwht::fwht_slice;
+    ///
+    /// // Majority of three variables, in ±1 form
+    /// let mut spectrum = vec![1i64, 1, 1, -1, 1, -1, -1, -1];
+    /// fwht_slice(&mut spectrum).unwrap();
+    ///
+    /// let metrics = WalshMetrics::from_spectrum(spectrum).unwrap();
+    /// assert_eq!(metrics.nonlinearity(), 2);
+    /// assert!(metrics.is_balanced());
+    /// ```
+    pub fn from_spectrum(spectrum: Vec<i64>) -> Result<Self, FwhtError> {
+        if spectrum.is_empty() || !is_valid_fwht_length(spectrum.len()) {
+            return Err(FwhtError::NotPowerOfTwo {
+                len: spectrum.len(),
+            });
+        }
+
+        let linearity = spectrum.iter().map(|w| w.unsigned_abs()).max().unwrap_or(0);
+        Ok(WalshMetrics {
+            num_vars: spectrum.len().trailing_zeros(),
+            spectrum,
+            linearity,
+        })
+    }
+
+    /// Number of variables `n`
+    pub fn num_vars(&self) -> u32 {
+        self.num_vars
+    }
+
+    /// The Walsh coefficients, indexed by the mask `a`
+    pub fn spectrum(&self) -> &[i64] {
+        &self.spectrum
+    }
+
+    /// Consumes the metrics and returns the spectrum
+    pub fn into_spectrum(self) -> Vec<i64> {
+        self.spectrum
+    }
+
+    /// Largest Walsh magnitude `max |W(a)|`
+    pub fn linearity(&self) -> u64 {
+        self.linearity
+    }
+
+    /// Hamming distance to the nearest affine function, `2^(n-1) - max |W(a)| / 2`
+    ///
+    /// Saturates at 0 for spectra that are not the spectrum of a Boolean
+    /// function.
+    pub fn nonlinearity(&self) -> u64 {
+        (self.spectrum.len() as u64).saturating_sub(self.linearity) / 2
+    }
+
+    /// Whether `|W(a)| = 2^(n/2)` for every `a`
+    ///
+    /// Bent functions reach the largest possible nonlinearity and only exist
+    /// for an even number of variables.
+    pub fn is_bent(&self) -> bool {
+        if !self.num_vars.is_multiple_of(2) {
+            return false;
+        }
+        let flat = 1u64 << (self.num_vars / 2);
+        self.spectrum.iter().all(|w| w.unsigned_abs() == flat)
+    }
+
+    /// Whether `f` takes the values 0 and 1 equally often, i.e. `W(0) = 0`
+    pub fn is_balanced(&self) -> bool {
+        self.spectrum[0] == 0
+    }
+
+    /// All affine functions at distance [`nonlinearity`](Self::nonlinearity) from `f`
+    ///
+    /// Each mask `a` with `|W(a)|` equal to the linearity yields one
+    /// approximation, with the constant chosen by the sign of `W(a)`. The
+    /// result is sorted by mask.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::{AffineApproximation, TruthTable};
+    ///
+    /// // x0 ⊕ x1·x2 agrees with x0 on 6 of 8 inputs, and with x0 ⊕ x1, x0 ⊕ x2
+    /// // and x0 ⊕ x1 ⊕ x2 ⊕ 1 on 6 as well
+    /// let f = TruthTable::from_fn(3, |x| (x ^ x >> 1 & x >> 2) & 1 == 1).unwrap();
+    /// let best = f.metrics().best_affine_approximations();
+    /// assert_eq!(best.len(), 4);
+    /// assert_eq!(
+    ///     best[3],
+    ///     AffineApproximation { mask: 0b111, constant: true, agreements: 6 }
+    /// );
+    /// ```
+    pub fn best_affine_approximations(&self) -> Vec<AffineApproximation> {
+        let len = self.spectrum.len() as u64;
+        self.spectrum
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| w.unsigned_abs() == self.linearity)
+            .map(|(mask, &w)| AffineApproximation {
+                mask,
+                constant: w < 0,
+                agreements: (len + self.linearity) / 2,
+            })
+            .collect()
+    }
+
+    /// Number of masks `a` with `W(a) ≠ 0`, grouped by the Hamming weight of `a`
+    ///
+    /// Entry `k` of the result, for `0 ≤ k ≤ n`, counts the masks of weight
+    /// `k` in the spectral support.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// // Majority correlates with each single variable and with all three
+    /// let majority = TruthTable::from_hex("e8").unwrap();
+    /// assert_eq!(majority.metrics().support_by_weight(), vec![0, 3, 0, 1]);
+    /// ```
+    pub fn support_by_weight(&self) -> Vec<u64> {
+        let mut counts = vec![0; self.num_vars as usize + 1];
+        for (mask, &w) in self.spectrum.iter().enumerate() {
+            if w != 0 {
+                counts[mask.count_ones() as usize] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Largest `t` such that `W(a) = 0` for every mask of weight `1..=t`
+    ///
+    /// A function is correlation immune of order `t` when its output is
+    /// statistically independent of every `t` of its inputs. This is one less
+    /// than the smallest weight of a nonzero mask in the spectral support;
+    /// constant functions have order `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// // x0 ⊕ x1 ⊕ x2·x3 is independent of any single input
+    /// let f = TruthTable::from_fn(4, |x| (x ^ x >> 1 ^ x >> 2 & x >> 3) & 1 == 1).unwrap();
+    /// assert_eq!(f.metrics().correlation_immunity_order(), 1);
+    /// ```
+    pub fn correlation_immunity_order(&self) -> u32 {
+        self.support_by_weight()
+            .iter()
+            .skip(1)
+            .position(|&count| count > 0)
+            .map_or(self.num_vars, |weight| weight as u32)
+    }
+
+    /// Resiliency order, or `None` if the function is not balanced
+    ///
+    /// A `t`-resilient function is balanced and correlation immune of order
+    /// `t`, i.e. `W(a) = 0` for every mask of weight `0..=t`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// // x0 ⊕ x1 ⊕ x2·x3 is balanced, so 1-resilient
+    /// let f = TruthTable::from_fn(4, |x| (x ^ x >> 1 ^ x >> 2 & x >> 3) & 1 == 1).unwrap();
+    /// assert_eq!(f.metrics().resiliency_order(), Some(1));
+    ///
+    /// // x0·x1 is correlation immune of order 0 and unbalanced
+    /// let and = TruthTable::from_hex("8").unwrap();
+    /// assert_eq!(and.metrics().resiliency_order(), None);
+    /// ```
+    pub fn resiliency_order(&self) -> Option<u32> {
+        self.is_balanced()
+            .then(|| self.correlation_immunity_order())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boolean::TruthTable;
+
+    /// Distance from `f` to every affine function, by direct comparison
+    fn affine_distances(f: &TruthTable) -> Vec<(usize, bool, u64)> {
+        let mut distances = Vec::new();
+        for mask in 0..f.len() {
+            for constant in [false, true] {
+                let distance = (0..f.len())
+                    .filter(|&x| f.eval(x) != ((x & mask).count_ones() % 2 == 1) ^ constant)
+                    .count();
+                distances.push((mask, constant, distance as u64));
+            }
+        }
+        distances
+    }
+
+    #[test]
+    fn test_metrics_match_brute_force() {
+        for hex in ["e8", "96", "7f", "00", "ac", "1ee1", "8ce3", "0ff0"] {
+            let f = TruthTable::from_hex(hex).unwrap();
+            let metrics = f.metrics();
+            let distances = affine_distances(&f);
+            let nonlinearity = distances.iter().map(|d| d.2).min().unwrap();
+
+            assert_eq!(metrics.nonlinearity(), nonlinearity, "{}", hex);
+            assert_eq!(
+                metrics.linearity(),
+                f.len() as u64 - 2 * nonlinearity,
+                "{}",
+                hex
+            );
+            assert_eq!(metrics.is_balanced(), f.weight() * 2 == f.len() as u64);
+
+            let best: Vec<_> = distances
+                .iter()
+                .filter(|d| d.2 == nonlinearity)
+                .map(|&(mask, constant, distance)| AffineApproximation {
+                    mask,
+                    constant,
+                    agreements: f.len() as u64 - distance,
+                })
+                .collect();
+            assert_eq!(metrics.best_affine_approximations(), best, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn test_bent_functions() {
+        // x0·x1 ⊕ x2·x3 ⊕ x4·x5
+        let bent = TruthTable::from_fn(6, |x| {
+            (x & x >> 1 ^ x >> 2 & x >> 3 ^ x >> 4 & x >> 5) & 1 == 1
+        })
+        .unwrap();
+        let metrics = bent.metrics();
+        assert!(metrics.is_bent());
+        assert_eq!(metrics.nonlinearity(), 28);
+        assert_eq!(metrics.best_affine_approximations().len(), 64);
+
+        let not_bent = TruthTable::from_fn(6, |x| (x & x >> 1 ^ x >> 2) & 1 == 1).unwrap();
+        assert!(!not_bent.metrics().is_bent());
+
+        // Odd variable counts are never bent
+        let majority = TruthTable::from_hex("e8").unwrap();
+        assert!(!majority.metrics().is_bent());
+    }
+
+    #[test]
+    fn test_affine_functions() {
+        // 1 ⊕ x0 ⊕ x2 is at distance 0 from itself only
+        let f = TruthTable::from_fn(3, |x| (1 ^ x ^ x >> 2) & 1 == 1).unwrap();
+        let metrics = f.metrics();
+        assert_eq!(metrics.nonlinearity(), 0);
+        assert_eq!(metrics.linearity(), 8);
+        assert!(metrics.is_balanced());
+        assert_eq!(
+            metrics.best_affine_approximations(),
+            vec![AffineApproximation {
+                mask: 0b101,
+                constant: true,
+                agreements: 8,
+            }]
+        );
+    }
+
+    /// Correlation immunity order by checking that `f` stays balanced on every
+    /// subcube fixing `t` inputs
+    fn correlation_immunity_brute_force(f: &TruthTable) -> u32 {
+        let n = f.num_vars();
+        let len = f.len();
+        let immune = |t: u32| {
+            (0..len).filter(|vars| vars.count_ones() == t).all(|vars| {
+                (0..len).filter(|fixed| fixed & !vars == 0).all(|fixed| {
+                    let ones = (0..len).filter(|x| x & vars == fixed && f.eval(*x)).count();
+                    let weight = f.weight() as usize;
+                    ones << t == weight
+                })
+            })
+        };
+        (1..=n).take_while(|&t| immune(t)).last().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_correlation_immunity_matches_brute_force() {
+        for hex in [
+            "e8", "96", "7f", "69", "00", "1ee1", "8ce3", "6996", "0ff0", "3cc3",
+        ] {
+            let f = TruthTable::from_hex(hex).unwrap();
+            let metrics = f.metrics();
+            assert_eq!(
+                metrics.correlation_immunity_order(),
+                correlation_immunity_brute_force(&f),
+                "{}",
+                hex
+            );
+        }
+    }
+
+    #[test]
+    fn test_support_by_weight() {
+        // x0 ⊕ x1 ⊕ x2·x3 correlates with x0 ⊕ x1 ⊕ (any of x2, x3 or none)
+        let f = TruthTable::from_fn(4, |x| (x ^ x >> 1 ^ x >> 2 & x >> 3) & 1 == 1).unwrap();
+        let metrics = f.metrics();
+        assert_eq!(metrics.support_by_weight(), vec![0, 0, 1, 2, 1]);
+        assert_eq!(metrics.correlation_immunity_order(), 1);
+        assert_eq!(metrics.resiliency_order(), Some(1));
+
+        let total: u64 = metrics.support_by_weight().iter().sum();
+        let nonzero = metrics.spectrum().iter().filter(|&&w| w != 0).count();
+        assert_eq!(total, nonzero as u64);
+    }
+
+    #[test]
+    fn test_resiliency_order() {
+        // The parity of all n inputs is (n - 1)-resilient
+        let parity = TruthTable::from_fn(5, |x| x.count_ones() % 2 == 1).unwrap();
+        assert_eq!(parity.metrics().correlation_immunity_order(), 4);
+        assert_eq!(parity.metrics().resiliency_order(), Some(4));
+
+        // Balanced but correlated with x0
+        let majority = TruthTable::from_hex("e8").unwrap();
+        assert_eq!(majority.metrics().correlation_immunity_order(), 0);
+        assert_eq!(majority.metrics().resiliency_order(), Some(0));
+
+        // x0 ⊕ x1 ⊕ x2 ⊕ x3 ⊕ x4 ⊕ x5·x6 on 7 variables is 4-resilient
+        let f = TruthTable::from_fn(7, |x| {
+            ((x ^ x >> 1 ^ x >> 2 ^ x >> 3 ^ x >> 4) ^ (x >> 5 & x >> 6)) & 1 == 1
+        })
+        .unwrap();
+        assert_eq!(f.metrics().resiliency_order(), Some(4));
+
+        // Constants are immune of every order but unbalanced
+        let zero = TruthTable::new(3).unwrap();
+        assert_eq!(zero.metrics().correlation_immunity_order(), 3);
+        assert_eq!(zero.metrics().resiliency_order(), None);
+
+        // Bent functions are never balanced
+        let bent = TruthTable::from_fn(4, |x| (x & x >> 1 ^ x >> 2 & x >> 3) & 1 == 1).unwrap();
+        assert_eq!(bent.metrics().correlation_immunity_order(), 0);
+        assert_eq!(bent.metrics().resiliency_order(), None);
+    }
+
+    #[test]
+    fn test_from_spectrum() {
+        let f = TruthTable::from_hex("8ce3").unwrap();
+        let metrics = WalshMetrics::from_spectrum(f.walsh_spectrum()).unwrap();
+        assert_eq!(metrics, f.metrics());
+        assert_eq!(metrics.num_vars(), 4);
+        assert_eq!(metrics.spectrum(), &f.walsh_spectrum()[..]);
+        assert_eq!(metrics.into_spectrum(), f.walsh_spectrum());
+
+        let single = WalshMetrics::from_spectrum(vec![-1]).unwrap();
+        assert_eq!(single.num_vars(), 0);
+        assert_eq!(single.nonlinearity(), 0);
+
+        assert_eq!(
+            WalshMetrics::from_spectrum(vec![]),
+            Err(FwhtError::NotPowerOfTwo { len: 0 })
+        );
+        assert_eq!(
+            WalshMetrics::from_spectrum(vec![0, 0, 0]),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+    }
+}
@@ -0,0 +1,15 @@
+//! Boolean functions and their spectra
+//!
+//! A Boolean function of `n` variables is stored as its truth table: bit `x`
+//! is the value `f(x)`, where bit `i` of the index `x` is the variable `xᵢ`.
+//! Tables are bit-packed into `u64` words, entry `x` in bit `x % 64` of word
+//! `x / 64`, so a function of `n ≤ 6` variables fits in a single word and
+//! tables with `2^30` entries take 128 MiB.
+
+pub mod anf;
+pub mod metrics;
+pub mod truth_table;
+
+pub use anf::{algebraic_degree, anf_from_truth_table, truth_table_from_anf};
+pub use metrics::{AffineApproximation, WalshMetrics};
+pub use truth_table::TruthTable;
@@ -0,0 +1,553 @@
+//! Bit-packed truth tables
+//!
+//! [`TruthTable`] stores a Boolean function of `n` variables in `2^n` bits
+//! instead of one integer per entry, and converts to the ±1 form only when
+//! the Walsh spectrum is computed.
+//!
+//! # Examples
+//!
+//! ```
+//! use fwht::boolean::TruthTable;
+//!
+//! let majority = TruthTable::from_hex("e8").unwrap();
+//! assert_eq!(majority.num_vars(), 3);
+//! assert!(majority.eval(0b011));
+//! assert!(!majority.eval(0b100));
+//! assert_eq!(majority.walsh_spectrum(), vec![0, 4, 4, 0, 4, 0, 0, -4]);
+//! ```
+
+use super::anf::{anf_degree, binary_mobius, table_words, valid_bits};
+use super::metrics::WalshMetrics;
+use crate::core::{fwht_unchecked, validate_length};
+use crate::error::FwhtError;
+use std::ops::{Add, Sub};
+
+/// A Boolean function of `n` variables, bit-packed into `u64` words
+///
+/// Entry `x` is bit `x % 64` of word `x / 64` (see the [module
+/// docs](super)). Bits beyond the `2^n` entries of small tables are always
+/// zero.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TruthTable {
+    num_vars: u32,
+    words: Vec<u64>,
+}
+
+impl TruthTable {
+    /// Returns the constant zero function of `num_vars` variables
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::Overflow`] if `2^num_vars` does not fit in `usize`.
+    pub fn new(num_vars: u32) -> Result<Self, FwhtError> {
+        Ok(TruthTable {
+            num_vars,
+            words: vec![0; table_words(num_vars)?],
+        })
+    }
+
+    /// Wraps bit-packed words as the truth table of `num_vars` variables
+    ///
+    /// Bits beyond the `2^num_vars` entries are cleared.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::LengthMismatch`] if `words` does not hold exactly
+    /// `max(1, 2^num_vars / 64)` words, and [`FwhtError::Overflow`] if
+    /// `2^num_vars` does not fit in `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// let and = TruthTable::from_words(2, vec![0b1000]).unwrap();
+    /// assert!(and.eval(3));
+    /// ```
+    pub fn from_words(num_vars: u32, mut words: Vec<u64>) -> Result<Self, FwhtError> {
+        let expected = table_words(num_vars)?;
+        if words.len() != expected {
+            return Err(FwhtError::LengthMismatch {
+                expected,
+                got: words.len(),
+            });
+        }
+        words[0] &= valid_bits(num_vars);
+        Ok(TruthTable { num_vars, words })
+    }
+
+    /// Builds the truth table of `f` on `num_vars` variables
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::Overflow`] if `2^num_vars` does not fit in `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// let parity = TruthTable::from_fn(4, |x| x.count_ones() % 2 == 1).unwrap();
+    /// assert_eq!(parity.to_hex(), "6996");
+    /// ```
+    pub fn from_fn<F: Fn(usize) -> bool>(num_vars: u32, f: F) -> Result<Self, FwhtError> {
+        let mut table = TruthTable::new(num_vars)?;
+        for x in (0..table.len()).filter(|&x| f(x)) {
+            table.words[x / 64] |= 1 << (x % 64);
+        }
+        Ok(table)
+    }
+
+    /// Builds a truth table from 0/1 values: every nonzero value is a 1
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::NotPowerOfTwo`] if the length is not a power of 2
+    /// (or is 0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// let xor = TruthTable::from_binary(&[0u8, 1, 1, 0]).unwrap();
+    /// assert_eq!(xor.to_hex(), "6");
+    /// ```
+    pub fn from_binary<T: PartialEq + Default>(values: &[T]) -> Result<Self, FwhtError> {
+        let zero = T::default();
+        Self::from_entries(values, |v| *v != zero)
+    }
+
+    /// Builds a truth table from ±1 values `(-1)^f(x)`: every negative value
+    /// is a 1
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::NotPowerOfTwo`] if the length is not a power of 2
+    /// (or is 0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// let and = TruthTable::from_signs(&[1.0, 1.0, 1.0, -1.0]).unwrap();
+    /// assert_eq!(and.to_hex(), "8");
+    /// ```
+    pub fn from_signs<T: PartialOrd + Default>(values: &[T]) -> Result<Self, FwhtError> {
+        let zero = T::default();
+        Self::from_entries(values, |v| *v < zero)
+    }
+
+    /// Parses a hexadecimal truth table, as printed in the literature
+    ///
+    /// The string is the table read as one big-endian number whose bit `x` is
+    /// `f(x)`: the last digit holds `f(3) f(2) f(1) f(0)`. A string of `d`
+    /// digits has `4·d` entries, so `d` must be a power of 2 and the function
+    /// has at least 2 variables. Upper and lower case digits are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::NotPowerOfTwo`] if the number of digits is not a
+    /// power of 2 (or is 0), and [`FwhtError::InvalidDigit`] if a character
+    /// is not a hexadecimal digit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// let f = TruthTable::from_hex("0F0F").unwrap();
+    /// assert_eq!(f.num_vars(), 4);
+    /// assert!(f.eval(0) && !f.eval(4));
+    /// ```
+    pub fn from_hex(hex: &str) -> Result<Self, FwhtError> {
+        let digits = hex.len();
+        if digits == 0 {
+            return Err(FwhtError::NotPowerOfTwo { len: 0 });
+        }
+        validate_length(digits)?;
+
+        let num_vars = (digits * 4).trailing_zeros();
+        let mut table = TruthTable::new(num_vars)?;
+        for (position, c) in hex.char_indices() {
+            let nibble = c.to_digit(16).ok_or(FwhtError::InvalidDigit { position })? as u64;
+            let k = digits - 1 - position;
+            table.words[k / 16] |= nibble << (4 * (k % 16));
+        }
+        Ok(table)
+    }
+
+    /// Formats the table as lowercase hexadecimal (see [`from_hex`])
+    ///
+    /// Tables of fewer than 2 variables are printed as a single digit.
+    ///
+    /// [`from_hex`]: TruthTable::from_hex
+    pub fn to_hex(&self) -> String {
+        let digits = self.len().div_ceil(4);
+        (0..digits)
+            .rev()
+            .map(|k| {
+                let nibble = (self.words[k / 16] >> (4 * (k % 16))) & 0xf;
+                char::from_digit(nibble as u32, 16).unwrap_or('0')
+            })
+            .collect()
+    }
+
+    /// Returns the table as 0/1 values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// let f = TruthTable::from_hex("8").unwrap();
+    /// assert_eq!(f.to_binary::<u8>(), vec![0, 0, 0, 1]);
+    /// ```
+    pub fn to_binary<T: From<u8>>(&self) -> Vec<T> {
+        self.entries().map(|bit| T::from(bit as u8)).collect()
+    }
+
+    /// Returns the table as ±1 values `(-1)^f(x)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// let f = TruthTable::from_hex("8").unwrap();
+    /// assert_eq!(f.to_signs::<i32>(), vec![1, 1, 1, -1]);
+    /// ```
+    pub fn to_signs<T: From<i8>>(&self) -> Vec<T> {
+        self.entries()
+            .map(|bit| T::from(if bit { -1 } else { 1 }))
+            .collect()
+    }
+
+    /// Returns `f(x)`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is not below `2^n`.
+    pub fn eval(&self, x: usize) -> bool {
+        assert!(
+            x < self.len(),
+            "input {} out of range for {} variables",
+            x,
+            self.num_vars
+        );
+        (self.words[x / 64] >> (x % 64)) & 1 == 1
+    }
+
+    /// Number of variables `n`
+    pub fn num_vars(&self) -> u32 {
+        self.num_vars
+    }
+
+    /// Number of entries `2^n`
+    pub fn len(&self) -> usize {
+        1 << self.num_vars
+    }
+
+    /// Always `false`: a table has at least one entry
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Number of inputs with `f(x) = 1`
+    pub fn weight(&self) -> u64 {
+        self.words.iter().map(|w| w.count_ones() as u64).sum()
+    }
+
+    /// The bit-packed words
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Consumes the table and returns the bit-packed words
+    pub fn into_words(self) -> Vec<u64> {
+        self.words
+    }
+
+    /// Returns the ANF coefficients, packed as a table
+    ///
+    /// Entry `S` of the result is the coefficient of the monomial `x_S`; see
+    /// [`anf_from_truth_table`](super::anf_from_truth_table).
+    pub fn anf(&self) -> TruthTable {
+        let mut anf = self.clone();
+        binary_mobius(&mut anf.words, self.num_vars);
+        anf
+    }
+
+    /// Algebraic degree of the function (0 for constants)
+    pub fn algebraic_degree(&self) -> u32 {
+        anf_degree(&self.anf().words, self.num_vars)
+    }
+
+    /// Walsh spectrum `W(a) = Σ (-1)^(f(x) ⊕ a·x)` over all `x`
+    ///
+    /// The table is expanded to ±1 directly into the returned vector, which
+    /// is then transformed in place, so the only allocation is the spectrum
+    /// itself: 8 bytes per entry. Use [`TruthTable::walsh_spectrum_into`] to
+    /// write a narrower type or reuse a buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// // The linear function x0 ⊕ x1 correlates only with a = 0b11
+    /// let xor = TruthTable::from_hex("6").unwrap();
+    /// assert_eq!(xor.walsh_spectrum(), vec![0, 0, 0, 4]);
+    /// ```
+    pub fn walsh_spectrum(&self) -> Vec<i64> {
+        let mut spectrum = self.to_signs::<i64>();
+        fwht_unchecked(&mut spectrum);
+        spectrum
+    }
+
+    /// Writes the Walsh spectrum into `spectrum`, allocating nothing
+    ///
+    /// Same values as [`TruthTable::walsh_spectrum`]. Coefficients range over
+    /// `±2^n`, so `T` must hold `2^n`: `i32` covers up to 30 variables and
+    /// `i16` up to 14, at a half or a quarter of the memory of `i64`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::LengthMismatch`] if `spectrum` does not have
+    /// `2^n` entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// let majority = TruthTable::from_hex("e8").unwrap();
+    /// let mut spectrum = [0i32; 8];
+    /// majority.walsh_spectrum_into(&mut spectrum).unwrap();
+    /// assert_eq!(spectrum, [0, 4, 4, 0, 4, 0, 0, -4]);
+    /// ```
+    pub fn walsh_spectrum_into<T>(&self, spectrum: &mut [T]) -> Result<(), FwhtError>
+    where
+        T: Add<Output = T> + Sub<Output = T> + Copy + From<i8>,
+    {
+        if spectrum.len() != self.len() {
+            return Err(FwhtError::LengthMismatch {
+                expected: self.len(),
+                got: spectrum.len(),
+            });
+        }
+
+        for (value, bit) in spectrum.iter_mut().zip(self.entries()) {
+            *value = T::from(if bit { -1 } else { 1 });
+        }
+        fwht_unchecked(spectrum);
+        Ok(())
+    }
+
+    /// Nonlinearity, bentness and the other [`WalshMetrics`] of the function
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::boolean::TruthTable;
+    ///
+    /// let majority = TruthTable::from_hex("e8").unwrap();
+    /// assert_eq!(majority.metrics().nonlinearity(), 2);
+    /// ```
+    pub fn metrics(&self) -> WalshMetrics {
+        WalshMetrics::from_spectrum(self.walsh_spectrum())
+            .expect("a truth table has a power-of-two length")
+    }
+
+    /// Correlation immunity order; see
+    /// [`WalshMetrics::correlation_immunity_order`]
+    pub fn correlation_immunity_order(&self) -> u32 {
+        self.metrics().correlation_immunity_order()
+    }
+
+    /// Resiliency order, or `None` if unbalanced; see
+    /// [`WalshMetrics::resiliency_order`]
+    pub fn resiliency_order(&self) -> Option<u32> {
+        self.metrics().resiliency_order()
+    }
+
+    /// Iterates over `f(0), f(1), …, f(2^n - 1)`
+    fn entries(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len()).map(|x| (self.words[x / 64] >> (x % 64)) & 1 == 1)
+    }
+
+    /// Builds a table from the predicate `is_one` on each entry
+    fn from_entries<T, F>(values: &[T], is_one: F) -> Result<Self, FwhtError>
+    where
+        F: Fn(&T) -> bool,
+    {
+        if values.is_empty() {
+            return Err(FwhtError::NotPowerOfTwo { len: 0 });
+        }
+        validate_length(values.len())?;
+
+        let mut table = TruthTable::new(values.len().trailing_zeros())?;
+        for (x, value) in values.iter().enumerate() {
+            table.words[x / 64] |= (is_one(value) as u64) << (x % 64);
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::FWHT;
+
+    fn sample(num_vars: u32) -> TruthTable {
+        TruthTable::from_fn(num_vars, |x| {
+            (x.wrapping_mul(0x9e37_79b9) >> 7).count_ones() % 3 == 0
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        for hex in [
+            "8",
+            "e8",
+            "6996",
+            "0123456789abcdef",
+            "7969817cc5893ba6ac326e47619f5ad0",
+        ] {
+            assert_eq!(TruthTable::from_hex(hex).unwrap().to_hex(), hex);
+        }
+        let long = "f0".repeat(64);
+        let table = TruthTable::from_hex(&long).unwrap();
+        assert_eq!(table.num_vars(), 9);
+        assert_eq!(table.to_hex(), long);
+        assert_eq!(
+            TruthTable::from_hex("ABCD").unwrap(),
+            TruthTable::from_hex("abcd").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_hex_bit_order() {
+        let table = TruthTable::from_hex("0001").unwrap();
+        assert!(table.eval(0));
+        assert_eq!(table.weight(), 1);
+
+        let table = TruthTable::from_hex("8000").unwrap();
+        assert!(table.eval(15));
+        assert_eq!(table.weight(), 1);
+    }
+
+    #[test]
+    fn test_conversions_round_trip() {
+        for num_vars in [0, 1, 3, 6, 8] {
+            let table = sample(num_vars);
+
+            let binary = table.to_binary::<u8>();
+            assert_eq!(TruthTable::from_binary(&binary).unwrap(), table);
+
+            let signs = table.to_signs::<f64>();
+            assert_eq!(TruthTable::from_signs(&signs).unwrap(), table);
+
+            let words = TruthTable::from_words(num_vars, table.clone().into_words()).unwrap();
+            assert_eq!(words, table);
+            assert_eq!(
+                table.weight(),
+                binary.iter().filter(|&&b| b == 1).count() as u64
+            );
+        }
+    }
+
+    #[test]
+    fn test_walsh_spectrum_matches_fwht() {
+        for num_vars in [0, 2, 6, 7, 10] {
+            let table = sample(num_vars);
+            let expected = table.to_signs::<i64>().fwht().unwrap();
+            assert_eq!(table.walsh_spectrum(), expected);
+        }
+    }
+
+    #[test]
+    fn test_walsh_spectrum_into_narrow_types() {
+        for num_vars in [0, 2, 6, 7, 10] {
+            let table = sample(num_vars);
+            let expected = table.walsh_spectrum();
+
+            let mut narrow = vec![0i32; table.len()];
+            table.walsh_spectrum_into(&mut narrow).unwrap();
+            assert!(narrow
+                .iter()
+                .zip(&expected)
+                .all(|(&a, &b)| i64::from(a) == b));
+
+            let mut short = vec![0i16; table.len()];
+            table.walsh_spectrum_into(&mut short).unwrap();
+            assert!(short
+                .iter()
+                .zip(&expected)
+                .all(|(&a, &b)| i64::from(a) == b));
+        }
+
+        let table = sample(3);
+        assert_eq!(
+            table.walsh_spectrum_into(&mut [0i32; 4]),
+            Err(FwhtError::LengthMismatch {
+                expected: 8,
+                got: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_anf_and_degree() {
+        let majority = TruthTable::from_hex("e8").unwrap();
+        assert_eq!(majority.anf().to_hex(), "68");
+        assert_eq!(majority.algebraic_degree(), 2);
+        assert_eq!(majority.anf().anf(), majority);
+    }
+
+    #[test]
+    fn test_from_words_clears_unused_bits() {
+        let table = TruthTable::from_words(2, vec![u64::MAX]).unwrap();
+        assert_eq!(table.words(), &[0b1111]);
+        assert_eq!(table.weight(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_eval_out_of_range() {
+        TruthTable::new(3).unwrap().eval(8);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            TruthTable::from_hex("abc"),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+        assert_eq!(
+            TruthTable::from_hex(""),
+            Err(FwhtError::NotPowerOfTwo { len: 0 })
+        );
+        assert_eq!(
+            TruthTable::from_hex("0g"),
+            Err(FwhtError::InvalidDigit { position: 1 })
+        );
+        assert_eq!(
+            TruthTable::from_binary::<u8>(&[]),
+            Err(FwhtError::NotPowerOfTwo { len: 0 })
+        );
+        assert_eq!(
+            TruthTable::from_signs(&[1, -1, 1]),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+        assert_eq!(
+            TruthTable::from_words(7, vec![0]),
+            Err(FwhtError::LengthMismatch {
+                expected: 2,
+                got: 1
+            })
+        );
+    }
+}
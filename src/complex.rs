@@ -0,0 +1,322 @@
+//! Complex-valued transforms (requires the "num-complex" feature)
+//!
+//! `Complex<T>` works with every generic entry point of the crate, including
+//! [`Normalization`], plans, and the SIMD kernels for `Complex<f32>` and
+//! `Complex<f64>`. This module adds helpers for the layouts and symmetries
+//! specific to complex data.
+//!
+//! # Conjugate symmetry
+//!
+//! The Hadamard matrix is real, so the transform commutes with conjugation
+//! and acts on the real and imaginary parts independently:
+//! `H·(a + ib) = H·a + i·H·b`. In particular the spectrum of a real signal is
+//! real, which is the Walsh counterpart of the conjugate-symmetric spectrum of
+//! a real signal under the DFT. [`fwht_real_pair`] uses this to transform two
+//! real signals with one complex transform.
+//!
+//! # Split layout
+//!
+//! Signals stored as separate real and imaginary arrays (structure of arrays)
+//! are transformed in place with [`fwht_split_mut`], without interleaving
+//! them first. [`to_split`] and [`from_split`] convert between the layouts.
+//!
+//! # Examples
+//!
+//! ```
+//! use fwht::complex::Complex;
+//! use fwht::FWHT;
+//!
+//! let mut data = vec![
+//!     Complex::new(1.0, 0.0),
+//!     Complex::new(0.0, 1.0),
+//!     Complex::new(1.0, 0.0),
+//!     Complex::new(0.0, -1.0),
+//! ];
+//! data.fwht_mut().unwrap();
+//! assert_eq!(data[0], Complex::new(2.0, 0.0));
+//! assert_eq!(data[1], Complex::new(2.0, 0.0));
+//! assert_eq!(data[3], Complex::new(0.0, -2.0));
+//! ```
+
+use crate::core::{fwht_slice, fwht_slice_normalized, validate_length};
+use crate::error::FwhtError;
+use crate::normalize::{Normalization, Normalize};
+use std::ops::{Add, Sub};
+
+pub use num_complex::Complex;
+
+/// FWHT of a complex signal stored as separate real and imaginary slices
+///
+/// Transforms both slices in place. This is the same transform as on the
+/// interleaved `Complex<T>` signal, since each part is transformed on its own.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::LengthMismatch`] if the slices have different
+/// lengths, and [`FwhtError::NotPowerOfTwo`] if their length is not a power
+/// of 2. Neither slice is modified on error.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::complex::fwht_split_mut;
+///
+/// let mut re = [1.0, 0.0, 1.0, 0.0];
+/// let mut im = [0.0, 1.0, 0.0, -1.0];
+/// fwht_split_mut(&mut re, &mut im).unwrap();
+/// assert_eq!(re, [2.0, 2.0, 0.0, 0.0]);
+/// assert_eq!(im, [0.0, 0.0, 2.0, -2.0]);
+/// ```
+pub fn fwht_split_mut<T>(re: &mut [T], im: &mut [T]) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + 'static,
+{
+    validate_split(re, im)?;
+    fwht_slice(re)?;
+    fwht_slice(im)
+}
+
+/// Normalized FWHT of a complex signal in split layout
+///
+/// # Errors
+///
+/// Returns the errors of [`fwht_split_mut`], and
+/// [`FwhtError::UnsupportedNormalization`] if the scale factor cannot be
+/// represented in `T`.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::complex::fwht_split_mut_normalized;
+/// use fwht::Normalization;
+///
+/// let mut re = [4.0, 0.0, 0.0, 0.0];
+/// let mut im = [0.0, 0.0, 0.0, 4.0];
+/// fwht_split_mut_normalized(&mut re, &mut im, Normalization::ByN).unwrap();
+/// assert_eq!(re, [1.0, 1.0, 1.0, 1.0]);
+/// assert_eq!(im, [1.0, -1.0, -1.0, 1.0]);
+/// ```
+pub fn fwht_split_mut_normalized<T>(
+    re: &mut [T],
+    im: &mut [T],
+    normalization: Normalization,
+) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Normalize,
+{
+    validate_split(re, im)?;
+    normalization.factor::<T>(re.len())?;
+    fwht_slice_normalized(re, normalization)?;
+    fwht_slice_normalized(im, normalization)
+}
+
+/// Transforms two real signals at once with a single complex transform
+///
+/// Packs `a` and `b` as `a + ib` and transforms the result. Because real
+/// signals have real spectra, the real parts of the output are the spectrum
+/// of `a` and the imaginary parts are the spectrum of `b`; separate them with
+/// [`to_split`].
+///
+/// # Errors
+///
+/// Returns [`FwhtError::LengthMismatch`] if the signals have different
+/// lengths, and [`FwhtError::NotPowerOfTwo`] if their length is not a power
+/// of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::complex::{fwht_real_pair, to_split};
+///
+/// let spectrum = fwht_real_pair(&[1.0, 1.0, 1.0, 0.0], &[0.0, 1.0, 0.0, 1.0]).unwrap();
+/// let (a, b) = to_split(&spectrum);
+/// assert_eq!(a, vec![3.0, 1.0, 1.0, -1.0]);
+/// assert_eq!(b, vec![2.0, -2.0, 0.0, 0.0]);
+/// ```
+pub fn fwht_real_pair<T>(a: &[T], b: &[T]) -> Result<Vec<Complex<T>>, FwhtError>
+where
+    T: Copy + 'static,
+    Complex<T>: Add<Output = Complex<T>> + Sub<Output = Complex<T>>,
+{
+    let mut packed = from_split(a, b)?;
+    fwht_slice(&mut packed)?;
+    Ok(packed)
+}
+
+/// Splits complex numbers into their real and imaginary parts
+///
+/// # Examples
+///
+/// ```
+/// use fwht::complex::{to_split, Complex};
+///
+/// let (re, im) = to_split(&[Complex::new(1, 2), Complex::new(3, 4)]);
+/// assert_eq!(re, vec![1, 3]);
+/// assert_eq!(im, vec![2, 4]);
+/// ```
+pub fn to_split<T: Copy>(data: &[Complex<T>]) -> (Vec<T>, Vec<T>) {
+    data.iter().map(|z| (z.re, z.im)).unzip()
+}
+
+/// Joins real and imaginary parts into complex numbers
+///
+/// # Errors
+///
+/// Returns [`FwhtError::LengthMismatch`] if the slices have different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::complex::{from_split, Complex};
+///
+/// let data = from_split(&[1, 3], &[2, 4]).unwrap();
+/// assert_eq!(data, vec![Complex::new(1, 2), Complex::new(3, 4)]);
+/// ```
+pub fn from_split<T: Copy>(re: &[T], im: &[T]) -> Result<Vec<Complex<T>>, FwhtError> {
+    if re.len() != im.len() {
+        return Err(FwhtError::LengthMismatch {
+            expected: re.len(),
+            got: im.len(),
+        });
+    }
+    Ok(re
+        .iter()
+        .zip(im)
+        .map(|(&re, &im)| Complex::new(re, im))
+        .collect())
+}
+
+/// Checks that split parts have equal, power-of-2 lengths
+fn validate_split<T>(re: &[T], im: &[T]) -> Result<(), FwhtError> {
+    if re.len() != im.len() {
+        return Err(FwhtError::LengthMismatch {
+            expected: re.len(),
+            got: im.len(),
+        });
+    }
+    validate_length(re.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plan::{FwhtPlan, PlanOptions};
+    use crate::simd::detected_backend;
+    use crate::traits::FWHT;
+
+    fn signal(n: usize) -> Vec<Complex<f64>> {
+        (0..n)
+            .map(|i| Complex::new((i % 7) as f64 - 3.0, (i % 3) as f64 * 0.5))
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_componentwise_transform() {
+        let data = signal(64);
+        let (mut re, mut im) = to_split(&data);
+        fwht_slice(&mut re).unwrap();
+        fwht_slice(&mut im).unwrap();
+
+        let spectrum = data.fwht().unwrap();
+        assert_eq!(spectrum, from_split(&re, &im).unwrap());
+    }
+
+    #[test]
+    fn test_split_matches_interleaved() {
+        let data = signal(32);
+        let (mut re, mut im) = to_split(&data);
+        fwht_split_mut(&mut re, &mut im).unwrap();
+        assert_eq!(from_split(&re, &im).unwrap(), data.fwht().unwrap());
+    }
+
+    #[test]
+    fn test_commutes_with_conjugation() {
+        let data = signal(16);
+        let conjugated: Vec<_> = data.iter().map(|z| z.conj()).collect();
+        let expected: Vec<_> = data.fwht().unwrap().iter().map(|z| z.conj()).collect();
+        assert_eq!(conjugated.fwht().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_normalized_round_trip() {
+        let data = signal(16);
+        for norm in [
+            Normalization::None,
+            Normalization::Orthonormal,
+            Normalization::ByN,
+        ] {
+            let spectrum = data.fwht_normalized(norm).unwrap();
+            let restored = spectrum.ifwht(norm).unwrap();
+            for (a, b) in restored.iter().zip(&data) {
+                assert!((a - b).norm() < 1e-12, "{:?}: {} != {}", norm, a, b);
+            }
+
+            let (mut re, mut im) = to_split(&data);
+            fwht_split_mut_normalized(&mut re, &mut im, norm).unwrap();
+            assert_eq!(from_split(&re, &im).unwrap(), spectrum);
+        }
+    }
+
+    #[test]
+    fn test_real_pair() {
+        let a: Vec<f64> = (0..8).map(|i| i as f64).collect();
+        let b: Vec<f64> = (0..8).map(|i| (i * i) as f64).collect();
+        let (spectrum_a, spectrum_b) = to_split(&fwht_real_pair(&a, &b).unwrap());
+        assert_eq!(spectrum_a, a.fwht().unwrap());
+        assert_eq!(spectrum_b, b.fwht().unwrap());
+    }
+
+    #[test]
+    fn test_plan_uses_complex() {
+        let data: Vec<Complex<f32>> = (0..256)
+            .map(|i| Complex::new(i as f32, -(i as f32)))
+            .collect();
+        let options = PlanOptions {
+            backend: Some(detected_backend()),
+            ..PlanOptions::default()
+        };
+        let plan = FwhtPlan::<Complex<f32>>::new(256, options).unwrap();
+        assert_eq!(plan.backend(), Some(detected_backend()));
+
+        let mut planned = data.clone();
+        plan.execute(&mut planned).unwrap();
+        assert_eq!(planned, data.fwht().unwrap());
+    }
+
+    #[test]
+    fn test_split_errors() {
+        let mut re = [1.0, 2.0, 3.0, 4.0];
+        let mut im = [1.0, 2.0];
+        assert_eq!(
+            fwht_split_mut(&mut re, &mut im),
+            Err(FwhtError::LengthMismatch {
+                expected: 4,
+                got: 2
+            })
+        );
+        assert_eq!(re, [1.0, 2.0, 3.0, 4.0]);
+
+        let mut re = [1.0, 2.0, 3.0];
+        let mut im = [1.0, 2.0, 3.0];
+        assert_eq!(
+            fwht_split_mut(&mut re, &mut im),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+
+        let mut re = [1, 2, 3, 4, 5, 6, 7, 8];
+        let mut im = [0; 8];
+        assert_eq!(
+            fwht_split_mut_normalized(&mut re, &mut im, Normalization::Orthonormal),
+            Err(FwhtError::UnsupportedNormalization { len: 8 })
+        );
+        assert_eq!(re, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        assert_eq!(
+            fwht_real_pair(&[1.0], &[]),
+            Err(FwhtError::LengthMismatch {
+                expected: 1,
+                got: 0
+            })
+        );
+    }
+}
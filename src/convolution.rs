@@ -0,0 +1,426 @@
+//! XOR (dyadic) convolution and correlation
+//!
+//! The XOR convolution of `a` and `b` is
+//!
+//! ```text
+//! c[k] = Σ a[i]·b[j]  over all i ^ j = k
+//! ```
+//!
+//! and their dyadic cross-correlation is `r[k] = Σ a[i]·b[i ^ k]`. Since
+//! `i ^ j = k` exactly when `j = i ^ k`, the two are the same sums; the
+//! correlation functions add the scaling conventions used for sequences.
+//!
+//! The Walsh-Hadamard transform diagonalizes the convolution, as the DFT does
+//! for cyclic convolution: `H·c = (H·a) ∘ (H·b)`. Every function here therefore
+//! transforms its inputs, multiplies the spectra pointwise, and applies the
+//! inverse transform, for `O(n log n)` work instead of `O(n²)`.
+//!
+//! Inputs of different lengths are zero-padded to the smallest common power
+//! of 2. The inverse divides by `n` through [`Normalization`], so results are
+//! exact for integers (the division has no remainder) and for
+//! [`ModInt`](crate::modular::ModInt) (which multiplies by `n⁻¹`). Integer
+//! products overflow as ordinary arithmetic does; use `ModInt` for exact
+//! results modulo a prime.
+//!
+//! # Examples
+//!
+//! ```
+//! use fwht::convolution::xor_convolve;
+//!
+//! // c[k] = Σ a[i]·b[i ^ k]
+//! let c = xor_convolve(&[1, 2, 3, 4], &[1, 0, 0, 1]).unwrap();
+//! assert_eq!(c, vec![5, 5, 5, 5]);
+//! ```
+
+use crate::core::{fwht_slice, ifwht_slice};
+use crate::error::FwhtError;
+use crate::normalize::{Normalization, Normalize};
+use std::ops::{Add, Mul, Sub};
+
+/// XOR convolution of two sequences
+///
+/// Both inputs are zero-padded to the smallest power of 2 that holds the
+/// longer one, which is also the length of the result. Empty inputs give an
+/// empty result.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::Overflow`] if the padded length does not fit in
+/// `usize`, and [`FwhtError::UnsupportedNormalization`] if `1/n` cannot be
+/// represented in `T` (for example `n = 256` with `i8`).
+///
+/// # Examples
+///
+/// ```
+/// use fwht::convolution::xor_convolve;
+///
+/// let c = xor_convolve(&[1.0, 2.0], &[0.0, 0.0, 1.0]).unwrap();
+/// assert_eq!(c, vec![0.0, 0.0, 1.0, 2.0]);
+/// ```
+pub fn xor_convolve<T>(a: &[T], b: &[T]) -> Result<Vec<T>, FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Normalize + Default + 'static,
+{
+    let mut result = a.to_vec();
+    xor_convolve_mut(&mut result, b)?;
+    Ok(result)
+}
+
+/// XOR convolution that stores the result in `a`
+///
+/// `a` is zero-padded in place to the common length; `b` is copied into a
+/// single scratch buffer of that length.
+///
+/// # Errors
+///
+/// Same as [`xor_convolve`]. `a` is left unchanged on error.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::convolution::xor_convolve_mut;
+///
+/// let mut a = vec![1, 2, 3, 4];
+/// xor_convolve_mut(&mut a, &[0, 1]).unwrap();
+/// assert_eq!(a, vec![2, 1, 4, 3]);
+/// ```
+pub fn xor_convolve_mut<T>(a: &mut Vec<T>, b: &[T]) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Normalize + Default + 'static,
+{
+    let n = padded_len(a.len().max(b.len()))?;
+    if n == 0 {
+        return Ok(());
+    }
+    Normalization::ByN.factor::<T>(n)?;
+
+    let mut scratch = padded(b, n);
+    a.resize(n, T::default());
+    fwht_slice(a)?;
+    fwht_slice(&mut scratch)?;
+    for (x, &y) in a.iter_mut().zip(&scratch) {
+        *x = *x * y;
+    }
+    ifwht_slice(a, Normalization::None)
+}
+
+/// `k`-fold XOR self-convolution `a ⊕ a ⊕ … ⊕ a`
+///
+/// Transforms `a` once, raises each coefficient to the power `k` by repeated
+/// squaring, and transforms back, so the cost does not grow with `k`. The
+/// result has the length of `a` padded to a power of 2; `k = 1` returns the
+/// padded input.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::InvalidExponent`] if `k` is 0, since the empty
+/// convolution (the unit impulse) needs a multiplicative identity, and the
+/// errors of [`xor_convolve`].
+///
+/// # Examples
+///
+/// ```
+/// use fwht::convolution::{xor_convolution_power, xor_convolve};
+/// use fwht::modular::ModInt;
+///
+/// type Mint = ModInt<998_244_353>;
+///
+/// let a: Vec<Mint> = [1, 1, 0, 1].into_iter().map(Mint::new).collect();
+/// let cube = xor_convolution_power(&a, 3).unwrap();
+/// let expected = xor_convolve(&xor_convolve(&a, &a).unwrap(), &a).unwrap();
+/// assert_eq!(cube, expected);
+///
+/// // Huge exponents cost the same as small ones
+/// let power = xor_convolution_power(&a, 1 << 60).unwrap();
+/// assert_eq!(power.len(), 4);
+/// ```
+pub fn xor_convolution_power<T>(a: &[T], k: u64) -> Result<Vec<T>, FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Normalize + Default + 'static,
+{
+    if k == 0 {
+        return Err(FwhtError::InvalidExponent { exponent: k });
+    }
+    let n = padded_len(a.len())?;
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    Normalization::ByN.factor::<T>(n)?;
+
+    let mut result = padded(a, n);
+    fwht_slice(&mut result)?;
+    for x in result.iter_mut() {
+        *x = pow(*x, k);
+    }
+    ifwht_slice(&mut result, Normalization::None)?;
+    Ok(result)
+}
+
+/// Dyadic cross-correlation `r[k] = Σ a[i]·b[i ^ k]`
+///
+/// Computed as the XOR convolution of `a` and `b` (see the [module
+/// docs](self)) and scaled by `normalization`: [`Normalization::None`] keeps
+/// the raw sums, [`Normalization::ByN`] divides them by the padded length
+/// `n` (so two equal ±1 sequences correlate to 1 at `k = 0`), and
+/// [`Normalization::Orthonormal`] divides them by `√n`. Integers divide with
+/// truncation, as in [`Normalize`].
+///
+/// The sums are bilinear; for complex sequences pass the conjugate of `b` to
+/// get the Hermitian correlation.
+///
+/// # Errors
+///
+/// Same as [`xor_convolve`], and [`FwhtError::UnsupportedNormalization`] if
+/// the scale factor cannot be represented in `T`.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::convolution::xor_cross_correlation;
+/// use fwht::Normalization;
+///
+/// let a = [1.0, -1.0, 1.0, 1.0];
+/// let b = [1.0, 1.0, -1.0, 1.0];
+/// let r = xor_cross_correlation(&a, &b, Normalization::ByN).unwrap();
+/// assert_eq!(r, vec![0.0, 0.0, 0.0, 1.0]);
+/// ```
+pub fn xor_cross_correlation<T>(
+    a: &[T],
+    b: &[T],
+    normalization: Normalization,
+) -> Result<Vec<T>, FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Normalize + Default + 'static,
+{
+    let factor = normalization.factor::<T>(padded_len(a.len().max(b.len()))?)?;
+    let mut result = a.to_vec();
+    xor_convolve_mut(&mut result, b)?;
+    scale(&mut result, factor);
+    Ok(result)
+}
+
+/// Dyadic autocorrelation `r[k] = Σ a[i]·a[i ^ k]`
+///
+/// Needs only two transforms: the spectrum of `a` is squared pointwise and
+/// transformed back (the Walsh counterpart of the Wiener-Khinchin theorem).
+/// The result is scaled by `normalization` as in [`xor_cross_correlation`].
+///
+/// # Errors
+///
+/// Same as [`xor_cross_correlation`].
+///
+/// # Examples
+///
+/// ```
+/// use fwht::convolution::xor_autocorrelation;
+/// use fwht::Normalization;
+///
+/// // A bent sequence: every nonzero shift is uncorrelated
+/// let r = xor_autocorrelation(&[1, 1, 1, -1], Normalization::None).unwrap();
+/// assert_eq!(r, vec![4, 0, 0, 0]);
+/// ```
+pub fn xor_autocorrelation<T>(a: &[T], normalization: Normalization) -> Result<Vec<T>, FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Normalize + Default + 'static,
+{
+    let factor = normalization.factor::<T>(padded_len(a.len())?)?;
+    let mut result = xor_convolution_power(a, 2)?;
+    scale(&mut result, factor);
+    Ok(result)
+}
+
+/// Applies an optional scale factor to every element
+fn scale<T: Normalize>(data: &mut [T], factor: Option<T::Factor>) {
+    if let Some(factor) = factor {
+        for x in data.iter_mut() {
+            *x = x.scale(factor);
+        }
+    }
+}
+
+/// Returns the smallest power of 2 holding `len` elements, or 0 for 0
+pub(crate) fn padded_len(len: usize) -> Result<usize, FwhtError> {
+    match len {
+        0 => Ok(0),
+        _ => len.checked_next_power_of_two().ok_or(FwhtError::Overflow),
+    }
+}
+
+/// Copies `data` into a new vector zero-padded to `n` elements
+pub(crate) fn padded<T: Copy + Default>(data: &[T], n: usize) -> Vec<T> {
+    let mut result = Vec::with_capacity(n);
+    result.extend_from_slice(data);
+    result.resize(n, T::default());
+    result
+}
+
+/// Returns `base` raised to the power `k ≥ 1` by repeated squaring
+fn pow<T: Mul<Output = T> + Copy>(base: T, k: u64) -> T {
+    let mut result = base;
+    for bit in (0..k.ilog2()).rev() {
+        result = result * result;
+        if (k >> bit) & 1 == 1 {
+            result = result * base;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modular::ModInt;
+
+    type Mint = ModInt<998_244_353>;
+
+    fn naive<T>(a: &[T], b: &[T]) -> Vec<T>
+    where
+        T: Add<Output = T> + Mul<Output = T> + Copy + Default,
+    {
+        let n = padded_len(a.len().max(b.len())).unwrap();
+        let (a, b) = (padded(a, n), padded(b, n));
+        let mut c = vec![T::default(); n];
+        for i in 0..n {
+            for j in 0..n {
+                c[i ^ j] = c[i ^ j] + a[i] * b[j];
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn test_matches_naive_integers() {
+        let a: Vec<i64> = (0..16).map(|i| (i * 7) % 5 - 2).collect();
+        let b: Vec<i64> = (0..16).map(|i| (i * i) % 11 - 5).collect();
+        assert_eq!(xor_convolve(&a, &b).unwrap(), naive(&a, &b));
+    }
+
+    #[test]
+    fn test_matches_naive_floats() {
+        let a: Vec<f64> = (0..8).map(|i| i as f64 * 0.5).collect();
+        let b: Vec<f64> = (0..8).map(|i| 1.0 - i as f64 * 0.25).collect();
+        let expected = naive(&a, &b);
+        for (x, y) in xor_convolve(&a, &b).unwrap().iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-12, "{} != {}", x, y);
+        }
+    }
+
+    #[test]
+    fn test_matches_naive_modular() {
+        let a: Vec<Mint> = (0..32u64).map(|i| Mint::new(i * 123_456_789)).collect();
+        let b: Vec<Mint> = (0..32u64).map(|i| Mint::new(i * i + 998_244_000)).collect();
+        assert_eq!(xor_convolve(&a, &b).unwrap(), naive(&a, &b));
+    }
+
+    #[test]
+    fn test_pads_to_common_length() {
+        let a = [1, 2, 3];
+        let b = [4, 5, 6, 7, 8];
+        let c = xor_convolve(&a, &b).unwrap();
+        assert_eq!(c.len(), 8);
+        assert_eq!(c, naive(&a, &b));
+
+        let mut in_place = a.to_vec();
+        xor_convolve_mut(&mut in_place, &b).unwrap();
+        assert_eq!(in_place, c);
+
+        assert_eq!(xor_convolve::<i32>(&[], &[]).unwrap(), Vec::<i32>::new());
+        assert_eq!(xor_convolve(&[], &[1, 2]).unwrap(), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_power_matches_repeated_convolution() {
+        let a = vec![1i64, 2, 0, 1, 3];
+        let mut expected = padded(&a, 8);
+        for k in 1..6 {
+            assert_eq!(xor_convolution_power(&a, k).unwrap(), expected, "k = {}", k);
+            expected = xor_convolve(&expected, &a).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_power_modular_large_exponent() {
+        let a: Vec<Mint> = [3, 1, 4, 1, 5, 9, 2, 6].map(Mint::new).to_vec();
+        let k = 1_000_000_007u64;
+        let power = xor_convolution_power(&a, k).unwrap();
+
+        // The spectrum of the power is the pointwise power of the spectrum
+        let mut spectrum = a.clone();
+        fwht_slice(&mut spectrum).unwrap();
+        let mut power_spectrum = power;
+        fwht_slice(&mut power_spectrum).unwrap();
+        for (x, y) in spectrum.iter().zip(&power_spectrum) {
+            assert_eq!(x.pow(k), *y);
+        }
+    }
+
+    #[test]
+    fn test_cross_correlation_matches_definition() {
+        let a: Vec<i64> = (0..8).map(|i| (i * 5) % 7 - 3).collect();
+        let b: Vec<i64> = (0..5).map(|i| i * i - 4).collect();
+        let b_padded = padded(&b, 8);
+        let expected: Vec<i64> = (0..8)
+            .map(|k| (0..8).map(|i| a[i] * b_padded[i ^ k]).sum())
+            .collect();
+
+        assert_eq!(
+            xor_cross_correlation(&a, &b, Normalization::None).unwrap(),
+            expected
+        );
+        assert_eq!(xor_convolve(&a, &b).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_cross_correlation_normalized() {
+        let a: Vec<f64> = (0..16)
+            .map(|i| if i % 3 == 0 { 1.0 } else { -1.0 })
+            .collect();
+        let raw = xor_cross_correlation(&a, &a, Normalization::None).unwrap();
+        let by_n = xor_cross_correlation(&a, &a, Normalization::ByN).unwrap();
+        let orthonormal = xor_cross_correlation(&a, &a, Normalization::Orthonormal).unwrap();
+
+        assert_eq!(by_n[0], 1.0);
+        for k in 0..16 {
+            assert_eq!(by_n[k], raw[k] / 16.0);
+            assert_eq!(orthonormal[k], raw[k] / 4.0);
+        }
+    }
+
+    #[test]
+    fn test_autocorrelation_matches_cross_correlation() {
+        let a: Vec<i32> = vec![1, -1, -1, 1, 1, 1, -1, 1, 1];
+        for norm in [Normalization::None, Normalization::ByN] {
+            assert_eq!(
+                xor_autocorrelation(&a, norm).unwrap(),
+                xor_cross_correlation(&a, &a, norm).unwrap()
+            );
+        }
+
+        let m: Vec<Mint> = (0..16u64).map(|i| Mint::new(i * 31 + 7)).collect();
+        assert_eq!(
+            xor_autocorrelation(&m, Normalization::ByN).unwrap(),
+            xor_cross_correlation(&m, &m, Normalization::ByN).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            xor_autocorrelation(&[1, 2, 3, 4, 5, 6, 7, 8], Normalization::Orthonormal),
+            Err(FwhtError::UnsupportedNormalization { len: 8 })
+        );
+        assert_eq!(
+            xor_convolution_power(&[1, 2], 0),
+            Err(FwhtError::InvalidExponent { exponent: 0 })
+        );
+
+        let mut a = vec![1i8; 256];
+        assert_eq!(
+            xor_convolve_mut(&mut a, &[1]),
+            Err(FwhtError::UnsupportedNormalization { len: 256 })
+        );
+        assert_eq!(a, vec![1i8; 256]);
+
+        assert_eq!(padded_len(usize::MAX), Err(FwhtError::Overflow));
+    }
+}
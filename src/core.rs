@@ -3,12 +3,22 @@
 //! This module contains the fundamental Fast Walsh-Hadamard Transform algorithm
 //! that operates on slices. All other implementations build upon this core function.
 
+use crate::error::FwhtError;
+use crate::normalize::{Normalization, Normalize};
+use crate::ordering::{bit_reverse_unchecked, reorder, Ordering};
+use crate::simd::{backend_kernel, selected_backend};
 use std::ops::{Add, Sub};
 
 /// Core FWHT algorithm that operates on mutable slices
 ///
 /// This is the fundamental implementation of the Fast Walsh-Hadamard Transform.
-/// It modifies the input slice in-place using the standard butterfly operations.
+/// For `f32`, `f64`, `i16`, `i32` (and complex floats with the "num-complex"
+/// feature) it runs the SIMD kernels of [`crate::simd`] with the
+/// [`selected_backend`](crate::simd::selected_backend), so
+/// [`force_backend`](crate::simd::force_backend) applies here too. Other
+/// types are transformed in place using radix-8 butterflies, which fuse
+/// three stages per pass over the data. Inputs larger than
+/// [`AUTO_BLOCKED_BYTES`] use the cache-blocked kernel (see [`Kernel`]).
 ///
 /// # Requirements
 ///
@@ -17,7 +27,7 @@ use std::ops::{Add, Sub};
 ///
 /// # Errors
 ///
-/// Returns an error if the input length is not a power of 2.
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of 2.
 ///
 /// # Examples
 ///
@@ -28,28 +38,449 @@ use std::ops::{Add, Sub};
 /// fwht_slice(&mut data).unwrap();
 /// assert_eq!(data, [3.0, 1.0, 1.0, -1.0]);
 /// ```
-pub fn fwht_slice<T>(data: &mut [T]) -> Result<(), &'static str>
+pub fn fwht_slice<T>(data: &mut [T]) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + 'static,
+{
+    validate_length(data.len())?;
+    match backend_kernel::<T>() {
+        Some(kernel) => kernel(data, selected_backend()),
+        None => fwht_unchecked(data),
+    }
+    Ok(())
+}
+
+/// Data size in bytes of the cache-resident tiles of [`Kernel::Blocked`]
+pub const BLOCK_BYTES: usize = 1 << 17;
+
+/// Contiguous row size in bytes used by [`Kernel::Blocked`] for the wide stages
+pub const ROW_BYTES: usize = 1 << 9;
+
+/// Tile size in bytes over which [`fwht_slice_into`] fuses the copy
+const INTO_TILE_BYTES: usize = 1 << 14;
+
+/// Data size in bytes above which [`Kernel::Auto`] selects [`Kernel::Blocked`]
+pub const AUTO_BLOCKED_BYTES: usize = 1 << 19;
+
+/// Butterfly kernel used to compute the transform
+///
+/// All kernels produce bit-identical results; they differ only in how they
+/// walk memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Kernel {
+    /// Chooses [`Kernel::Blocked`] when the data exceeds
+    /// [`AUTO_BLOCKED_BYTES`], and [`Kernel::Radix8`] otherwise
+    #[default]
+    Auto,
+    /// Radix-2 butterflies, one full pass over the data per stage
+    Radix2,
+    /// Radix-4 butterflies, fusing two stages per pass (plus a final
+    /// radix-2 stage when `log2 n` is odd)
+    Radix4,
+    /// Radix-8 butterflies, fusing three stages per pass (plus a final
+    /// radix-2 or radix-4 stage when needed)
+    Radix8,
+    /// Cache-blocked radix-8 butterflies that run several stages per
+    /// cache-resident tile of [`BLOCK_BYTES`]
+    Blocked,
+}
+
+impl Kernel {
+    /// Returns the concrete kernel used for `n` elements of type `T`
+    ///
+    /// ```
+    /// use fwht::core::Kernel;
+    ///
+    /// assert_eq!(Kernel::Auto.resolve::<f64>(1 << 10), Kernel::Radix8);
+    /// assert_eq!(Kernel::Auto.resolve::<f64>(1 << 20), Kernel::Blocked);
+    /// assert_eq!(Kernel::Radix2.resolve::<f64>(1 << 20), Kernel::Radix2);
+    /// ```
+    pub fn resolve<T>(self, n: usize) -> Kernel {
+        match self {
+            Kernel::Auto if n.saturating_mul(std::mem::size_of::<T>()) > AUTO_BLOCKED_BYTES => {
+                Kernel::Blocked
+            }
+            Kernel::Auto => Kernel::Radix8,
+            kernel => kernel,
+        }
+    }
+
+    /// Number of full passes over `n` elements of type `T` made by this kernel
+    ///
+    /// A pass reads and writes every element once. For inputs larger than
+    /// the cache, each pass goes to main memory, so this is a proxy for the
+    /// memory traffic of the transform.
+    ///
+    /// ```
+    /// use fwht::core::Kernel;
+    ///
+    /// let n = 1 << 24;
+    /// assert_eq!(Kernel::Radix2.memory_passes::<f64>(n), 24);
+    /// assert_eq!(Kernel::Blocked.memory_passes::<f64>(n), 3);
+    /// ```
+    pub fn memory_passes<T>(self, n: usize) -> usize {
+        if n <= 1 {
+            return 0;
+        }
+
+        let log2 = n.trailing_zeros() as usize;
+        match self.resolve::<T>(n) {
+            Kernel::Blocked => {
+                let (block, width) = blocked_geometry::<T>();
+                let block_log2 = block.trailing_zeros() as usize;
+                if log2 <= block_log2 {
+                    return 1;
+                }
+                let per_pass = (block / width).trailing_zeros() as usize;
+                1 + (log2 - block_log2).div_ceil(per_pass)
+            }
+            Kernel::Radix4 => log2.div_ceil(2),
+            Kernel::Radix8 => log2.div_ceil(3),
+            _ => log2,
+        }
+    }
+}
+
+/// FWHT on a mutable slice using a specific kernel
+///
+/// [`fwht_slice`] uses [`Kernel::Auto`]; this function allows choosing the
+/// kernel explicitly. Every kernel gives bit-identical results.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::core::{fwht_slice_with_kernel, Kernel};
+///
+/// let mut data = vec![1.0; 1 << 20];
+/// fwht_slice_with_kernel(&mut data, Kernel::Blocked).unwrap();
+/// assert_eq!(data[0], (1 << 20) as f64);
+/// ```
+pub fn fwht_slice_with_kernel<T>(data: &mut [T], kernel: Kernel) -> Result<(), FwhtError>
 where
     T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    validate_length(data.len())?;
+    stages_with_kernel(data, 1, data.len(), kernel);
+    Ok(())
+}
+
+/// FWHT on a mutable slice with the given normalization
+///
+/// Works like [`fwht_slice`], but scales the result according to
+/// `normalization`. The scaling is fused into the last butterfly stage, so it
+/// costs no extra pass over the data.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of 2,
+/// and [`FwhtError::UnsupportedNormalization`] if the scale factor cannot be
+/// represented in `T`.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::core::fwht_slice_normalized;
+/// use fwht::Normalization;
+///
+/// let mut data = [1.0, 1.0, 1.0, 0.0];
+/// fwht_slice_normalized(&mut data, Normalization::Orthonormal).unwrap();
+/// assert_eq!(data, [1.5, 0.5, 0.5, -0.5]);
+/// ```
+pub fn fwht_slice_normalized<T>(
+    data: &mut [T],
+    normalization: Normalization,
+) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Normalize,
 {
     let n = data.len();
+    validate_length(n)?;
 
-    if n == 0 {
-        return Ok(());
+    match normalization.factor::<T>(n)? {
+        Some(factor) => {
+            stages_with_kernel(data, 1, n / 2, Kernel::Auto);
+            last_stage_scaled(data, factor);
+        }
+        None => fwht_unchecked(data),
     }
 
-    if !n.is_power_of_two() {
-        return Err("Input length must be a power of 2");
+    Ok(())
+}
+
+/// Inverse FWHT on a mutable slice
+///
+/// Exactly inverts [`fwht_slice_normalized`] called with the same
+/// `normalization`.
+///
+/// # Errors
+///
+/// Same as [`fwht_slice_normalized`].
+///
+/// # Examples
+///
+/// ```
+/// use fwht::core::{fwht_slice, ifwht_slice};
+/// use fwht::Normalization;
+///
+/// let mut data = [1i32, 2, 3, 4];
+/// fwht_slice(&mut data).unwrap();
+/// ifwht_slice(&mut data, Normalization::None).unwrap();
+/// assert_eq!(data, [1, 2, 3, 4]);
+/// ```
+pub fn ifwht_slice<T>(data: &mut [T], normalization: Normalization) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Normalize,
+{
+    fwht_slice_normalized(data, normalization.inverse())
+}
+
+/// FWHT on a mutable slice with the coefficients in the given ordering
+///
+/// Computes the natural-order transform and then permutes the spectrum in
+/// place (see [`crate::ordering`]). [`Ordering::Sequency`] runs
+/// [`fwht_slice_sequency`] instead, which permutes the input once up front.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::core::fwht_slice_ordered;
+/// use fwht::Ordering;
+///
+/// let mut data = [1.0, 2.0, 3.0, 4.0];
+/// fwht_slice_ordered(&mut data, Ordering::Sequency).unwrap();
+/// assert_eq!(data, [10.0, -4.0, 0.0, -2.0]);
+/// ```
+pub fn fwht_slice_ordered<T>(data: &mut [T], ordering: Ordering) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + 'static,
+{
+    match ordering {
+        Ordering::Sequency => fwht_slice_sequency(data),
+        _ => {
+            fwht_slice(data)?;
+            reorder(data, Ordering::Natural, ordering)
+        }
     }
+}
 
-    let mut h = 1;
-    while h < n {
+/// Sequency-ordered FWHT on a mutable slice
+///
+/// Produces the Walsh (sequency) ordered spectrum with a single permutation
+/// pass, where [`fwht_slice`] followed by [`crate::ordering::reorder`] needs a
+/// bit reversal and a Gray-code permutation. This is a Manz-style kernel: the
+/// input is bit-reversed in place (the one remaining permutation pass), then
+/// the butterfly stages run from the widest to the narrowest, and each
+/// butterfly swaps its sum and difference outputs when the next-higher
+/// address bit is set. That bit already holds a sequency bit, so the swap
+/// turns the dyadic index into its Gray-decoded sequency index on the fly,
+/// and no permutation is needed after the stages.
+///
+/// The result is identical to [`fwht_slice_ordered`] with
+/// [`Ordering::Sequency`] and runs in O(n log n) time, in place.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::core::fwht_slice_sequency;
+///
+/// let mut data = [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+/// fwht_slice_sequency(&mut data).unwrap();
+/// assert_eq!(data, [4.0, 4.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+/// ```
+pub fn fwht_slice_sequency<T>(data: &mut [T]) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    validate_length(data.len())?;
+    sequency_unchecked(data);
+    Ok(())
+}
+
+/// Runs the sequency-ordered kernel on a slice whose length is already validated
+///
+/// The bit reversal of the input is a separate pass before the stages.
+pub(crate) fn sequency_unchecked<T>(data: &mut [T])
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    bit_reverse_unchecked(data);
+    sequency_stages(data, 1);
+}
+
+/// Runs the sequency-ordered kernel and applies `factor` in its last stage
+pub(crate) fn sequency_scaled<T>(data: &mut [T], factor: T::Factor)
+where
+    T: Add<Output = T> + Sub<Output = T> + Normalize,
+{
+    bit_reverse_unchecked(data);
+    sequency_stages(data, 2);
+
+    // Last stage (h = 1): the swap bit is bit 1 of the pair index
+    for (pair, chunk) in data.chunks_exact_mut(2).enumerate() {
+        let x = chunk[0];
+        let y = chunk[1];
+        let (a, b) = if pair & 1 != 0 {
+            (x - y, x + y)
+        } else {
+            (x + y, x - y)
+        };
+        chunk[0] = a.scale(factor);
+        chunk[1] = b.scale(factor);
+    }
+}
+
+/// Sequency butterfly stages from `h = n / 2` down to `h = last`, on
+/// bit-reversed input
+fn sequency_stages<T>(data: &mut [T], last: usize)
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    let n = data.len();
+    let mut h = n / 2;
+    while h >= last {
         for i in (0..n).step_by(h * 2) {
+            // Address bit above this stage, constant over the block
+            let swap = i & (h * 2) != 0;
             for j in i..i + h {
                 let x = data[j];
                 let y = data[j + h];
-                data[j] = x + y;
-                data[j + h] = x - y;
+                if swap {
+                    data[j] = x - y;
+                    data[j + h] = x + y;
+                } else {
+                    data[j] = x + y;
+                    data[j + h] = x - y;
+                }
+            }
+        }
+        h /= 2;
+    }
+}
+
+/// Out-of-place FWHT of `src` into `dst`
+///
+/// `src` is left untouched. The copy is fused into the transform: the first
+/// butterfly stage reads from `src` and writes to `dst`, and the following
+/// stages run on cache-sized tiles of `dst` while they are still hot, so the
+/// copy does not cost an extra pass over memory.
+///
+/// # Errors
+///
+/// - [`FwhtError::NotPowerOfTwo`] if `src.len()` is not a power of 2
+/// - [`FwhtError::LengthMismatch`] if `dst.len() != src.len()`
+///
+/// # Examples
+///
+/// ```
+/// use fwht::core::fwht_slice_into;
+///
+/// let src = [1.0, 1.0, 1.0, 0.0];
+/// let mut dst = [0.0; 4];
+/// fwht_slice_into(&src, &mut dst).unwrap();
+/// assert_eq!(dst, [3.0, 1.0, 1.0, -1.0]);
+/// ```
+pub fn fwht_slice_into<T>(src: &[T], dst: &mut [T]) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    let n = src.len();
+    validate_length(n)?;
+    if dst.len() != n {
+        return Err(FwhtError::LengthMismatch {
+            expected: n,
+            got: dst.len(),
+        });
+    }
+    if n <= 1 {
+        dst.copy_from_slice(src);
+        return Ok(());
+    }
+
+    let tile = floor_power_of_two(INTO_TILE_BYTES / std::mem::size_of::<T>().max(1)).clamp(2, n);
+    for (src, dst) in src.chunks_exact(tile).zip(dst.chunks_exact_mut(tile)) {
+        for (s, d) in src.chunks_exact(2).zip(dst.chunks_exact_mut(2)) {
+            d[0] = s[0] + s[1];
+            d[1] = s[0] - s[1];
+        }
+        radix8_stages(dst, 2, tile);
+    }
+    stages_with_kernel(dst, tile, n, Kernel::Auto);
+
+    Ok(())
+}
+
+/// FWHT of the `len` elements `data[offset + k * stride]`, in place
+///
+/// Elements between the strided positions are left untouched, so one channel
+/// of an interleaved buffer can be transformed without copying it out.
+///
+/// # Errors
+///
+/// - [`FwhtError::NotPowerOfTwo`] if `len` is not a power of 2
+/// - [`FwhtError::InvalidStride`] if `stride` is 0 and `len > 1`
+/// - [`FwhtError::LengthMismatch`] if the last strided element is past the
+///   end of `data`, with the required length as `expected`
+///
+/// # Examples
+///
+/// ```
+/// use fwht::core::fwht_strided;
+///
+/// // Left and right channels interleaved
+/// let mut data = [1.0, 5.0, 1.0, 5.0, 1.0, 5.0, 0.0, 5.0];
+/// fwht_strided(&mut data, 0, 2, 4).unwrap();
+/// assert_eq!(data, [3.0, 5.0, 1.0, 5.0, 1.0, 5.0, -1.0, 5.0]);
+/// ```
+pub fn fwht_strided<T>(
+    data: &mut [T],
+    offset: usize,
+    stride: usize,
+    len: usize,
+) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    validate_length(len)?;
+    if len == 0 {
+        return Ok(());
+    }
+    if stride == 0 && len > 1 {
+        return Err(FwhtError::InvalidStride { stride });
+    }
+
+    let end = (len - 1)
+        .checked_mul(stride)
+        .and_then(|last| offset.checked_add(1).and_then(|end| last.checked_add(end)))
+        .ok_or(FwhtError::Overflow)?;
+    if end > data.len() {
+        return Err(FwhtError::LengthMismatch {
+            expected: end,
+            got: data.len(),
+        });
+    }
+
+    let span = &mut data[offset..end];
+    let mut h = 1;
+    while h < len {
+        let step = h * stride;
+        for block in (0..span.len()).step_by(2 * step) {
+            for a in (block..block + step).step_by(stride) {
+                let x = span[a];
+                let y = span[a + step];
+                span[a] = x + y;
+                span[a + step] = x - y;
             }
         }
         h *= 2;
@@ -58,6 +489,390 @@ where
     Ok(())
 }
 
+/// FWHT of every channel of an interleaved buffer, in place
+///
+/// `data` holds `channels` signals interleaved sample by sample
+/// (`[l0, r0, l1, r1, ...]` for stereo). Each channel is transformed
+/// separately; the butterflies combine whole frames of `channels` samples at
+/// once, so the channels are never de-interleaved and the stages vectorize
+/// across them.
+///
+/// # Errors
+///
+/// - [`FwhtError::InvalidStride`] if `channels` is 0
+/// - [`FwhtError::LengthMismatch`] if `data.len()` is not a multiple of
+///   `channels`, with the next length holding a power-of-2 number of frames
+///   as `expected`
+/// - [`FwhtError::NotPowerOfTwo`] if the number of frames is not a power of 2
+///
+/// # Examples
+///
+/// ```
+/// use fwht::core::fwht_interleaved;
+///
+/// let mut stereo = [1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 0.0, 1.0];
+/// fwht_interleaved(&mut stereo, 2).unwrap();
+/// assert_eq!(stereo, [3.0, 2.0, 1.0, -2.0, 1.0, 0.0, -1.0, 0.0]);
+/// ```
+pub fn fwht_interleaved<T>(data: &mut [T], channels: usize) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    if channels == 0 {
+        return Err(FwhtError::InvalidStride { stride: channels });
+    }
+    if !data.len().is_multiple_of(channels) {
+        let expected = data
+            .len()
+            .div_ceil(channels)
+            .checked_next_power_of_two()
+            .and_then(|frames| frames.checked_mul(channels))
+            .ok_or(FwhtError::Overflow)?;
+        return Err(FwhtError::LengthMismatch {
+            expected,
+            got: data.len(),
+        });
+    }
+    validate_length(data.len() / channels)?;
+
+    interleaved_stages(data, channels);
+    Ok(())
+}
+
+/// Checks that `len` elements split into runs of `n`
+///
+/// On failure, `expected` is the next length that does, or 0 if `n` is 0.
+pub(crate) fn validate_multiple(len: usize, n: usize) -> Result<(), FwhtError> {
+    if n == 0 && len == 0 || n != 0 && len.is_multiple_of(n) {
+        return Ok(());
+    }
+    let expected = match n {
+        0 => 0,
+        _ => len.checked_next_multiple_of(n).ok_or(FwhtError::Overflow)?,
+    };
+    Err(FwhtError::LengthMismatch { expected, got: len })
+}
+
+/// Checks that `n` is a valid FWHT length
+pub(crate) fn validate_length(n: usize) -> Result<(), FwhtError> {
+    if is_valid_fwht_length(n) {
+        Ok(())
+    } else {
+        Err(FwhtError::NotPowerOfTwo { len: n })
+    }
+}
+
+/// Runs the full transform on a slice whose length is already validated
+pub(crate) fn fwht_unchecked<T>(data: &mut [T])
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    stages_with_kernel(data, 1, data.len(), Kernel::Auto);
+}
+
+/// Runs the stages with half-width `from <= h < until` using `kernel`
+pub(crate) fn stages_with_kernel<T>(data: &mut [T], from: usize, until: usize, kernel: Kernel)
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    match kernel.resolve::<T>(data.len()) {
+        Kernel::Blocked => blocked_stages(data, from, until),
+        Kernel::Radix4 => radix4_stages(data, from, until),
+        Kernel::Radix8 => radix8_stages(data, from, until),
+        _ => butterfly_stages(data, from, until),
+    }
+}
+
+/// Transforms `lanes` interleaved vectors stored element-major
+///
+/// Element `i` of vector `b` is at `data[i * lanes + b]`. Every butterfly
+/// then combines two runs of `lanes` contiguous elements, so the stages
+/// vectorize across the vectors instead of within one. `data.len() / lanes`
+/// must be a power of 2.
+pub(crate) fn interleaved_stages<T>(data: &mut [T], lanes: usize)
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    radix8_stages(data, lanes, data.len());
+}
+
+/// Largest power of 2 not greater than `n.max(1)`
+fn floor_power_of_two(n: usize) -> usize {
+    1 << n.max(1).ilog2()
+}
+
+/// Block length and row width (in elements) of the blocked kernel for `T`
+fn blocked_geometry<T>() -> (usize, usize) {
+    let size = std::mem::size_of::<T>().max(1);
+    let block = floor_power_of_two(BLOCK_BYTES / size).max(2);
+    let width = floor_power_of_two(ROW_BYTES / size).min(block / 2);
+    (block, width)
+}
+
+/// Cache-blocked butterfly stages with half-width `from <= h < until`
+///
+/// First every `block`-sized chunk runs all of its internal stages while it
+/// stays in cache. The remaining stages are then grouped `log2(block / width)`
+/// at a time: for each group, `block / width` rows of `width` contiguous
+/// elements spaced `h` apart hold every element those stages combine, so the
+/// whole group runs on one cache-resident tile before moving on. Both phases
+/// fuse stages into radix-8 butterflies.
+///
+/// Each element goes through the same butterflies in the same stage order as
+/// in [`butterfly_stages`], so the results are bit-identical.
+fn blocked_stages<T>(data: &mut [T], from: usize, until: usize)
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    let (block, width) = blocked_geometry::<T>();
+    if data.len() <= block || until <= block {
+        radix8_stages(data, from, until);
+        return;
+    }
+
+    for chunk in data.chunks_exact_mut(block) {
+        radix8_stages(chunk, from, block);
+    }
+
+    let max_stages = (block / width).trailing_zeros();
+    let mut h = block.max(from);
+    while h < until {
+        let stages = max_stages.min((until / h).trailing_zeros());
+        let rows = 1 << stages;
+        let span = h * rows;
+
+        for group in data.chunks_exact_mut(span) {
+            for column in (0..h).step_by(width) {
+                let tile = &mut group[column..];
+                let mut t = 1;
+                while t * 4 < rows {
+                    for first in (0..rows).step_by(8 * t) {
+                        for row in first..first + t {
+                            radix8_butterflies(&mut tile[row * h..], t * h, width);
+                        }
+                    }
+                    t *= 8;
+                }
+                if t * 2 < rows {
+                    for first in (0..rows).step_by(4 * t) {
+                        for row in first..first + t {
+                            radix4_butterflies(&mut tile[row * h..], t * h, width);
+                        }
+                    }
+                } else if t < rows {
+                    for row in 0..t {
+                        let (lo, hi) = tile[row * h..].split_at_mut(t * h);
+                        butterfly_pairs(&mut lo[..width], &mut hi[..width]);
+                    }
+                }
+            }
+        }
+
+        h = span;
+    }
+}
+
+/// Runs the radix-2 butterfly stages with half-width `from <= h < until`
+fn butterfly_stages<T>(data: &mut [T], from: usize, until: usize)
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    pair_stages(data, from, until, butterfly_pairs);
+}
+
+/// Runs radix-2 stages with half-width `from <= h < until` and a custom
+/// butterfly
+///
+/// `butterfly` receives the two contiguous halves `(lo, hi)` of each block
+/// of `2h` elements. With [`butterfly_pairs`] this is the Walsh-Hadamard
+/// transform; other butterflies give its siblings on the Boolean lattice.
+pub(crate) fn pair_stages<T, F>(data: &mut [T], from: usize, until: usize, butterfly: F)
+where
+    F: Fn(&mut [T], &mut [T]),
+{
+    let mut h = from;
+    while h < until {
+        for block in data.chunks_exact_mut(h * 2) {
+            let (lo, hi) = block.split_at_mut(h);
+            butterfly(lo, hi);
+        }
+        h *= 2;
+    }
+}
+
+/// Runs the stages with half-width `from <= h < until`, fusing pairs into
+/// radix-4
+///
+/// Each radix-4 butterfly combines stages `h` and `2h` on four elements
+/// spaced `h` apart, halving the loads and stores. An odd number of stages
+/// ends with one radix-2 stage.
+fn radix4_stages<T>(data: &mut [T], from: usize, until: usize)
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    let mut h = from;
+    while h * 2 < until {
+        for block in data.chunks_exact_mut(h * 4) {
+            radix4_butterflies(block, h, h);
+        }
+        h *= 4;
+    }
+    if h < until {
+        radix2_stage(data, h);
+    }
+}
+
+/// Runs the stages with half-width `from <= h < until`, fusing triples into
+/// radix-8
+///
+/// Each radix-8 butterfly combines stages `h`, `2h` and `4h` on eight
+/// elements spaced `h` apart. The remaining one or two stages run as a
+/// radix-2 or radix-4 stage.
+fn radix8_stages<T>(data: &mut [T], from: usize, until: usize)
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    let mut h = from;
+    while h * 4 < until {
+        for block in data.chunks_exact_mut(h * 8) {
+            radix8_butterflies(block, h, h);
+        }
+        h *= 8;
+    }
+    if h * 2 < until {
+        for block in data.chunks_exact_mut(h * 4) {
+            radix4_butterflies(block, h, h);
+        }
+    } else if h < until {
+        radix2_stage(data, h);
+    }
+}
+
+/// Runs the single radix-2 stage with half-width `h`
+fn radix2_stage<T>(data: &mut [T], h: usize)
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    for block in data.chunks_exact_mut(h * 2) {
+        let (lo, hi) = block.split_at_mut(h);
+        butterfly_pairs(lo, hi);
+    }
+}
+
+/// Applies stages `stride` and `2 * stride` to four rows of `width`
+/// elements starting `stride` apart in `block`
+fn radix4_butterflies<T>(block: &mut [T], stride: usize, width: usize)
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    let (q0, rest) = block.split_at_mut(stride);
+    let (q1, rest) = rest.split_at_mut(stride);
+    let (q2, q3) = rest.split_at_mut(stride);
+    let (q0, q1, q2, q3) = (
+        &mut q0[..width],
+        &mut q1[..width],
+        &mut q2[..width],
+        &mut q3[..width],
+    );
+
+    for (((a, b), c), d) in q0.iter_mut().zip(q1).zip(q2).zip(q3) {
+        let s0 = *a + *b;
+        let d0 = *a - *b;
+        let s1 = *c + *d;
+        let d1 = *c - *d;
+        *a = s0 + s1;
+        *b = d0 + d1;
+        *c = s0 - s1;
+        *d = d0 - d1;
+    }
+}
+
+/// Applies stages `stride`, `2 * stride` and `4 * stride` to eight rows of
+/// `width` elements starting `stride` apart in `block`
+fn radix8_butterflies<T>(block: &mut [T], stride: usize, width: usize)
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    let (q0, rest) = block.split_at_mut(stride);
+    let (q1, rest) = rest.split_at_mut(stride);
+    let (q2, rest) = rest.split_at_mut(stride);
+    let (q3, rest) = rest.split_at_mut(stride);
+    let (q4, rest) = rest.split_at_mut(stride);
+    let (q5, rest) = rest.split_at_mut(stride);
+    let (q6, q7) = rest.split_at_mut(stride);
+    let (q0, q1, q2, q3) = (
+        &mut q0[..width],
+        &mut q1[..width],
+        &mut q2[..width],
+        &mut q3[..width],
+    );
+    let (q4, q5, q6, q7) = (
+        &mut q4[..width],
+        &mut q5[..width],
+        &mut q6[..width],
+        &mut q7[..width],
+    );
+
+    for j in 0..width {
+        // Stage h
+        let a0 = q0[j] + q1[j];
+        let a1 = q0[j] - q1[j];
+        let a2 = q2[j] + q3[j];
+        let a3 = q2[j] - q3[j];
+        let a4 = q4[j] + q5[j];
+        let a5 = q4[j] - q5[j];
+        let a6 = q6[j] + q7[j];
+        let a7 = q6[j] - q7[j];
+
+        // Stage 2h
+        let b0 = a0 + a2;
+        let b1 = a1 + a3;
+        let b2 = a0 - a2;
+        let b3 = a1 - a3;
+        let b4 = a4 + a6;
+        let b5 = a5 + a7;
+        let b6 = a4 - a6;
+        let b7 = a5 - a7;
+
+        // Stage 4h
+        q0[j] = b0 + b4;
+        q1[j] = b1 + b5;
+        q2[j] = b2 + b6;
+        q3[j] = b3 + b7;
+        q4[j] = b0 - b4;
+        q5[j] = b1 - b5;
+        q6[j] = b2 - b6;
+        q7[j] = b3 - b7;
+    }
+}
+
+/// Applies one butterfly to each pair `(lo[j], hi[j])`
+pub(crate) fn butterfly_pairs<T>(lo: &mut [T], hi: &mut [T])
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    for (a, b) in lo.iter_mut().zip(hi.iter_mut()) {
+        let x = *a;
+        let y = *b;
+        *a = x + y;
+        *b = x - y;
+    }
+}
+
+/// Runs the last butterfly stage (`h = n / 2`) and applies `factor`
+pub(crate) fn last_stage_scaled<T>(data: &mut [T], factor: T::Factor)
+where
+    T: Add<Output = T> + Sub<Output = T> + Normalize,
+{
+    let (lo, hi) = data.split_at_mut(data.len() / 2);
+    for (a, b) in lo.iter_mut().zip(hi.iter_mut()) {
+        let x = *a;
+        let y = *b;
+        *a = (x + y).scale(factor);
+        *b = (x - y).scale(factor);
+    }
+}
+
 /// Validates that a length is suitable for FWHT
 ///
 /// Returns `true` if the length is a power of 2 (including 0 and 1).
@@ -87,6 +902,25 @@ mod tests {
         assert_eq!(data, [3f64, 1f64, 1f64, -1f64]);
     }
 
+    #[test]
+    fn test_fwht_slice_simd_types_match_generic() {
+        for log2 in [3, 6, 12, 17] {
+            let n = 1usize << log2;
+            let input: Vec<i32> = (0..n).map(|i| (i % 13) as i32 - 6).collect();
+            let mut expected = input.clone();
+            fwht_unchecked(&mut expected);
+
+            let mut ints = input.clone();
+            fwht_slice(&mut ints).unwrap();
+            assert_eq!(ints, expected);
+
+            let mut floats: Vec<f64> = input.iter().map(|&x| f64::from(x)).collect();
+            fwht_slice(&mut floats).unwrap();
+            let expected: Vec<f64> = expected.iter().map(|&x| f64::from(x)).collect();
+            assert_eq!(floats, expected);
+        }
+    }
+
     #[test]
     fn test_fwht_slice_size_8() {
         let mut data = [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
@@ -144,7 +978,386 @@ mod tests {
         let mut data = [1.0, 2.0, 3.0];
         let result = fwht_slice(&mut data);
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Input length must be a power of 2");
+        assert_eq!(result.unwrap_err(), FwhtError::NotPowerOfTwo { len: 3 });
+    }
+
+    #[test]
+    fn test_fwht_slice_normalized_by_n() {
+        let mut data = [1.0, 2.0, 3.0, 4.0];
+        fwht_slice_normalized(&mut data, Normalization::ByN).unwrap();
+        assert_eq!(data, [2.5, -0.5, -1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_fwht_slice_normalized_none_matches_raw() {
+        let mut raw = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let mut normalized = raw;
+        fwht_slice(&mut raw).unwrap();
+        fwht_slice_normalized(&mut normalized, Normalization::None).unwrap();
+        assert_eq!(raw, normalized);
+    }
+
+    #[test]
+    fn test_orthonormal_is_self_inverse() {
+        let original = [1.0f64, -2.0, 3.5, 4.0, 0.0, 6.0, -7.0, 8.0];
+        let mut data = original;
+        fwht_slice_normalized(&mut data, Normalization::Orthonormal).unwrap();
+        fwht_slice_normalized(&mut data, Normalization::Orthonormal).unwrap();
+
+        for (actual, expected) in data.iter().zip(original.iter()) {
+            assert!((actual - expected).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_ifwht_slice_inverts_every_mode() {
+        let original = [1.0f64, -2.0, 3.5, 4.0, 0.0, 6.0, -7.0, 8.0];
+        for norm in [
+            Normalization::None,
+            Normalization::Orthonormal,
+            Normalization::ByN,
+        ] {
+            let mut data = original;
+            fwht_slice_normalized(&mut data, norm).unwrap();
+            ifwht_slice(&mut data, norm).unwrap();
+
+            for (actual, expected) in data.iter().zip(original.iter()) {
+                assert!((actual - expected).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ifwht_slice_integers_exact() {
+        let mut data = [5i64, -3, 7, 0, 2, 2, -9, 1];
+        fwht_slice(&mut data).unwrap();
+        ifwht_slice(&mut data, Normalization::None).unwrap();
+        assert_eq!(data, [5, -3, 7, 0, 2, 2, -9, 1]);
+    }
+
+    #[test]
+    fn test_fwht_slice_normalized_unsupported() {
+        let mut data = [1i32, 2];
+        let result = fwht_slice_normalized(&mut data, Normalization::Orthonormal);
+        assert_eq!(
+            result.unwrap_err(),
+            FwhtError::UnsupportedNormalization { len: 2 }
+        );
+        assert_eq!(data, [1, 2]);
+    }
+
+    #[test]
+    fn test_fwht_slice_normalized_single_element() {
+        let mut data = [42.0];
+        fwht_slice_normalized(&mut data, Normalization::ByN).unwrap();
+        assert_eq!(data, [42.0]);
+    }
+
+    #[test]
+    fn test_fwht_slice_ordered() {
+        let input = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+
+        let mut natural = input;
+        fwht_slice_ordered(&mut natural, Ordering::Natural).unwrap();
+        assert_eq!(natural, [36.0, -4.0, -8.0, 0.0, -16.0, 0.0, 0.0, 0.0]);
+
+        let mut dyadic = input;
+        fwht_slice_ordered(&mut dyadic, Ordering::Dyadic).unwrap();
+        assert_eq!(dyadic, [36.0, -16.0, -8.0, 0.0, -4.0, 0.0, 0.0, 0.0]);
+
+        let mut sequency = input;
+        fwht_slice_ordered(&mut sequency, Ordering::Sequency).unwrap();
+        assert_eq!(sequency, [36.0, -16.0, 0.0, -8.0, 0.0, 0.0, 0.0, -4.0]);
+    }
+
+    #[test]
+    fn test_fwht_slice_sequency_matches_permuted_natural() {
+        for log2 in 0..11 {
+            let n = 1usize << log2;
+            let input: Vec<i64> = (0..n as i64).map(|i| (i * 7919) % 23 - 11).collect();
+
+            let mut expected = input.clone();
+            fwht_slice(&mut expected).unwrap();
+            reorder(&mut expected, Ordering::Natural, Ordering::Sequency).unwrap();
+
+            let mut direct = input;
+            fwht_slice_sequency(&mut direct).unwrap();
+            assert_eq!(direct, expected, "length {}", n);
+        }
+    }
+
+    #[test]
+    fn test_fwht_slice_sequency_non_power_of_two() {
+        let mut data = [1.0, 2.0, 3.0];
+        assert_eq!(
+            fwht_slice_sequency(&mut data),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+    }
+
+    #[test]
+    fn test_blocked_matches_radix2() {
+        for log2 in [0, 1, 5, 14, 15, 16, 17, 19] {
+            let input: Vec<f64> = (0..1usize << log2)
+                .map(|i| ((i * 40503) % 977) as f64 * 0.01 - 4.0)
+                .collect();
+
+            let mut radix2 = input.clone();
+            fwht_slice_with_kernel(&mut radix2, Kernel::Radix2).unwrap();
+            let mut blocked = input;
+            fwht_slice_with_kernel(&mut blocked, Kernel::Blocked).unwrap();
+
+            assert!(
+                radix2
+                    .iter()
+                    .zip(blocked.iter())
+                    .all(|(a, b)| a.to_bits() == b.to_bits()),
+                "length 2^{}",
+                log2
+            );
+        }
+    }
+
+    #[test]
+    fn test_blocked_small_elements() {
+        use std::num::Wrapping;
+
+        // Two-byte elements give a different block and row geometry
+        let input: Vec<Wrapping<i16>> = (0..1usize << 19)
+            .map(|i| Wrapping((i % 3) as i16 - 1))
+            .collect();
+
+        let mut radix2 = input.clone();
+        fwht_slice_with_kernel(&mut radix2, Kernel::Radix2).unwrap();
+        let mut blocked = input;
+        fwht_slice_with_kernel(&mut blocked, Kernel::Blocked).unwrap();
+        assert_eq!(blocked, radix2);
+    }
+
+    #[test]
+    fn test_blocked_normalized() {
+        let input: Vec<f64> = (0..1usize << 17).map(|i| (i % 5) as f64).collect();
+
+        let mut expected = input.clone();
+        butterfly_stages(&mut expected, 1, 1 << 16);
+        last_stage_scaled(&mut expected, 1.0 / (1 << 17) as f64);
+
+        let mut data = input;
+        fwht_slice_normalized(&mut data, Normalization::ByN).unwrap();
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn test_higher_radix_bit_identical_to_radix2() {
+        for log2 in 0..=12 {
+            let input: Vec<f64> = (0..1usize << log2)
+                .map(|i| ((i * 2654435761) % 1000) as f64 * 1e-3 - 0.5)
+                .collect();
+
+            let mut radix2 = input.clone();
+            fwht_slice_with_kernel(&mut radix2, Kernel::Radix2).unwrap();
+
+            for kernel in [Kernel::Radix4, Kernel::Radix8] {
+                let mut data = input.clone();
+                fwht_slice_with_kernel(&mut data, kernel).unwrap();
+                assert!(
+                    data.iter()
+                        .zip(radix2.iter())
+                        .all(|(a, b)| a.to_bits() == b.to_bits()),
+                    "{:?} at length 2^{}",
+                    kernel,
+                    log2
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip_odd_and_even_log2() {
+        for log2 in 1..=9 {
+            let input: Vec<i64> = (0..1i64 << log2).map(|i| i % 7 - 3).collect();
+
+            let mut data = input.clone();
+            fwht_slice(&mut data).unwrap();
+            ifwht_slice(&mut data, Normalization::None).unwrap();
+            assert_eq!(data, input, "length 2^{}", log2);
+        }
+    }
+
+    #[test]
+    fn test_memory_passes() {
+        assert_eq!(Kernel::Radix2.memory_passes::<f32>(1 << 20), 20);
+        assert_eq!(Kernel::Blocked.memory_passes::<f64>(1 << 10), 1);
+        assert_eq!(Kernel::Blocked.memory_passes::<f64>(1 << 22), 2);
+        assert_eq!(Kernel::Auto.memory_passes::<f64>(1 << 10), 4);
+        assert_eq!(Kernel::Radix4.memory_passes::<f64>(1 << 11), 6);
+        assert_eq!(Kernel::Radix8.memory_passes::<f64>(1 << 11), 4);
+        assert_eq!(Kernel::Auto.memory_passes::<f64>(1 << 28), 3);
+        assert_eq!(Kernel::Blocked.memory_passes::<f64>(1), 0);
+    }
+
+    #[test]
+    fn test_fwht_slice_with_kernel_errors() {
+        let mut data = [1.0, 2.0, 3.0];
+        assert_eq!(
+            fwht_slice_with_kernel(&mut data, Kernel::Blocked),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+    }
+
+    #[test]
+    fn test_into_matches_in_place() {
+        for log2 in [0, 1, 2, 3, 7, 11, 12, 13, 17] {
+            let src: Vec<f64> = (0..1usize << log2)
+                .map(|i| ((i * 2654435761) % 1000) as f64 * 1e-3 - 0.5)
+                .collect();
+
+            let mut expected = src.clone();
+            fwht_slice(&mut expected).unwrap();
+
+            let mut dst = vec![f64::NAN; src.len()];
+            fwht_slice_into(&src, &mut dst).unwrap();
+            assert!(
+                dst.iter()
+                    .zip(expected.iter())
+                    .all(|(a, b)| a.to_bits() == b.to_bits()),
+                "length 2^{}",
+                log2
+            );
+        }
+    }
+
+    #[test]
+    fn test_into_errors() {
+        let mut dst = [0.0; 4];
+        assert_eq!(
+            fwht_slice_into(&[1.0; 3], &mut dst),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+        assert_eq!(
+            fwht_slice_into(&[1.0; 8], &mut dst),
+            Err(FwhtError::LengthMismatch {
+                expected: 8,
+                got: 4
+            })
+        );
+        fwht_slice_into::<f64>(&[], &mut []).unwrap();
+    }
+
+    #[test]
+    fn test_strided_matches_gathered() {
+        let data: Vec<f64> = (0..200).map(|i| ((i * 37) % 23) as f64 - 11.0).collect();
+
+        for (offset, stride, len) in [(0, 1, 64), (3, 3, 32), (1, 5, 16), (7, 12, 16), (0, 2, 1)] {
+            let mut expected: Vec<f64> = (0..len).map(|k| data[offset + k * stride]).collect();
+            fwht_slice(&mut expected).unwrap();
+
+            let mut strided = data.clone();
+            fwht_strided(&mut strided, offset, stride, len).unwrap();
+
+            for (i, (&got, &original)) in strided.iter().zip(data.iter()).enumerate() {
+                let k = (i - offset.min(i)) / stride;
+                if i >= offset && (i - offset) % stride == 0 && k < len {
+                    assert_eq!(got, expected[k], "element {}", i);
+                } else {
+                    assert_eq!(got, original, "element {} must be untouched", i);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_strided_errors() {
+        let mut data = [1.0; 10];
+        assert_eq!(
+            fwht_strided(&mut data, 0, 2, 3),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+        assert_eq!(
+            fwht_strided(&mut data, 0, 0, 4),
+            Err(FwhtError::InvalidStride { stride: 0 })
+        );
+        fwht_strided(&mut data, 3, 2, 4).unwrap();
+        assert_eq!(
+            fwht_strided(&mut data, 4, 2, 4),
+            Err(FwhtError::LengthMismatch {
+                expected: 11,
+                got: 10
+            })
+        );
+        assert_eq!(
+            fwht_strided(&mut data, 1, usize::MAX, 2),
+            Err(FwhtError::Overflow)
+        );
+        assert_eq!(
+            fwht_strided(&mut data, usize::MAX, 1, 1),
+            Err(FwhtError::Overflow)
+        );
+        assert_eq!(
+            fwht_strided(&mut data, usize::MAX, 1, 2),
+            Err(FwhtError::Overflow)
+        );
+        fwht_strided(&mut data, 10, 0, 0).unwrap();
+        fwht_strided(&mut data, 9, 0, 1).unwrap();
+        assert_eq!(
+            fwht_strided(&mut data, 10, 1, 1),
+            Err(FwhtError::LengthMismatch {
+                expected: 11,
+                got: 10
+            })
+        );
+    }
+
+    #[test]
+    fn test_interleaved_channels() {
+        for channels in [1, 2, 3, 8] {
+            let frames = 32;
+            let input: Vec<i64> = (0..frames * channels as i64)
+                .map(|i| (i * 7) % 13 - 6)
+                .collect();
+
+            let mut expected = input.clone();
+            for channel in 0..channels {
+                fwht_strided(&mut expected, channel, channels, frames as usize).unwrap();
+            }
+
+            let mut data = input;
+            fwht_interleaved(&mut data, channels).unwrap();
+            assert_eq!(data, expected, "{} channels", channels);
+        }
+    }
+
+    #[test]
+    fn test_interleaved_errors() {
+        let mut data = [1.0; 12];
+        assert_eq!(
+            fwht_interleaved(&mut data, 0),
+            Err(FwhtError::InvalidStride { stride: 0 })
+        );
+        assert_eq!(
+            fwht_interleaved::<f64>(&mut [], 0),
+            Err(FwhtError::InvalidStride { stride: 0 })
+        );
+        assert_eq!(
+            fwht_interleaved(&mut data, 5),
+            Err(FwhtError::LengthMismatch {
+                expected: 20,
+                got: 12
+            })
+        );
+        assert_eq!(
+            fwht_interleaved(&mut data, 7),
+            Err(FwhtError::LengthMismatch {
+                expected: 14,
+                got: 12
+            })
+        );
+        assert_eq!(
+            fwht_interleaved(&mut data, 2),
+            Err(FwhtError::NotPowerOfTwo { len: 6 })
+        );
+        fwht_interleaved(&mut data, 3).unwrap();
+        fwht_interleaved::<f64>(&mut [], 3).unwrap();
     }
 
     #[test]
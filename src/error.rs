@@ -0,0 +1,195 @@
+//! Error type for FWHT operations
+//!
+//! This module defines [`FwhtError`], the single error type returned by every
+//! fallible entry point of the crate.
+
+use crate::simd::Backend;
+use std::error::Error;
+use std::fmt;
+
+/// Errors that can occur while applying a Fast Walsh-Hadamard Transform
+///
+/// Every fallible function and trait method in this crate returns this type,
+/// so callers can match on the variants instead of comparing messages, and
+/// propagate it with `?` into any `std::error::Error` based error stack.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::{FwhtError, FWHT};
+///
+/// let mut data = vec![1.0, 2.0, 3.0];
+/// match data.fwht_mut() {
+///     Err(FwhtError::NotPowerOfTwo { len }) => assert_eq!(len, 3),
+///     other => panic!("unexpected result: {:?}", other),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FwhtError {
+    /// The input length is not a power of 2
+    NotPowerOfTwo {
+        /// Length of the rejected input
+        len: usize,
+    },
+    /// The container is not laid out contiguously in memory
+    ///
+    /// No function returns this any more: non-contiguous arrays are now
+    /// accepted everywhere.
+    #[deprecated(note = "no function returns this variant any more")]
+    NonContiguous,
+    /// An intermediate size or value does not fit in the target type
+    Overflow,
+    /// Two inputs that must have the same length do not
+    LengthMismatch {
+        /// Length required by the operation
+        expected: usize,
+        /// Length that was actually provided
+        got: usize,
+    },
+    /// An axis index is out of bounds for the array's dimensionality
+    InvalidAxis {
+        /// The requested axis
+        axis: usize,
+        /// Number of dimensions of the array
+        ndim: usize,
+    },
+    /// The requested SIMD backend is not supported by the running CPU
+    UnsupportedBackend {
+        /// The requested backend
+        backend: Backend,
+    },
+    /// The requested normalization cannot be represented in the element type
+    UnsupportedNormalization {
+        /// Length of the transform
+        len: usize,
+    },
+    /// A stride or channel count of 0 was given for a non-empty transform
+    InvalidStride {
+        /// The rejected stride
+        stride: usize,
+    },
+    /// A modular transform was given a modulus of 0
+    InvalidModulus {
+        /// The rejected modulus
+        modulus: u64,
+    },
+    /// A convolution power was requested with an exponent of 0
+    InvalidExponent {
+        /// The rejected exponent
+        exponent: u64,
+    },
+    /// A string contains a character that is not a hexadecimal digit
+    InvalidDigit {
+        /// Byte offset of the character in the string
+        position: usize,
+    },
+    /// A wisdom file line could not be parsed
+    InvalidWisdom {
+        /// One-based line number of the malformed entry
+        line: usize,
+    },
+    /// A container does not expose its elements as a slice
+    ///
+    /// Returned by the default bodies of [`FWHT`](crate::FWHT) methods when
+    /// [`FWHT::as_fwht_slice`](crate::FWHT::as_fwht_slice) returns `None`.
+    NoSliceAccess,
+}
+
+impl fmt::Display for FwhtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FwhtError::NotPowerOfTwo { len } => {
+                write!(f, "input length must be a power of 2, got {}", len)
+            }
+            #[allow(deprecated)]
+            FwhtError::NonContiguous => write!(f, "array must be contiguous for FWHT"),
+            FwhtError::Overflow => write!(f, "arithmetic overflow in FWHT"),
+            FwhtError::LengthMismatch { expected, got } => {
+                write!(f, "length mismatch: expected {}, got {}", expected, got)
+            }
+            FwhtError::InvalidAxis { axis, ndim } => write!(
+                f,
+                "axis {} is out of bounds for an array with {} dimensions",
+                axis, ndim
+            ),
+            FwhtError::UnsupportedBackend { backend } => {
+                write!(f, "SIMD backend {} is not supported by this CPU", backend)
+            }
+            FwhtError::UnsupportedNormalization { len } => write!(
+                f,
+                "normalization for length {} is not representable in the element type",
+                len
+            ),
+            FwhtError::InvalidStride { stride } => {
+                write!(f, "stride must be at least 1, got {}", stride)
+            }
+            FwhtError::InvalidModulus { modulus } => {
+                write!(f, "modulus must be at least 1, got {}", modulus)
+            }
+            FwhtError::InvalidExponent { exponent } => {
+                write!(f, "exponent must be at least 1, got {}", exponent)
+            }
+            FwhtError::InvalidDigit { position } => {
+                write!(f, "invalid hexadecimal digit at position {}", position)
+            }
+            FwhtError::InvalidWisdom { line } => {
+                write!(f, "invalid wisdom entry on line {}", line)
+            }
+            FwhtError::NoSliceAccess => {
+                write!(f, "container does not expose its elements as a slice")
+            }
+        }
+    }
+}
+
+impl Error for FwhtError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_not_power_of_two() {
+        let err = FwhtError::NotPowerOfTwo { len: 3 };
+        assert_eq!(err.to_string(), "input length must be a power of 2, got 3");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_display_non_contiguous() {
+        assert_eq!(
+            FwhtError::NonContiguous.to_string(),
+            "array must be contiguous for FWHT"
+        );
+    }
+
+    #[test]
+    fn test_display_no_slice_access() {
+        assert_eq!(
+            FwhtError::NoSliceAccess.to_string(),
+            "container does not expose its elements as a slice"
+        );
+    }
+
+    #[test]
+    fn test_display_length_mismatch() {
+        let err = FwhtError::LengthMismatch {
+            expected: 4,
+            got: 2,
+        };
+        assert_eq!(err.to_string(), "length mismatch: expected 4, got 2");
+    }
+
+    #[test]
+    fn test_error_trait_object() {
+        fn fails() -> Result<(), Box<dyn Error>> {
+            Err(FwhtError::Overflow)?;
+            Ok(())
+        }
+
+        let err = fails().unwrap_err();
+        assert_eq!(err.to_string(), "arithmetic overflow in FWHT");
+        assert_eq!(err.downcast_ref::<FwhtError>(), Some(&FwhtError::Overflow));
+    }
+}
@@ -3,7 +3,14 @@
 //! This module provides the function-based API that works with any type
 //! implementing `AsMut<[T]>` and `Clone`.
 
-use crate::core::fwht_slice;
+use crate::core::{
+    fwht_slice, fwht_slice_into, fwht_slice_normalized, fwht_slice_ordered, ifwht_slice,
+};
+use crate::error::FwhtError;
+use crate::normalize::{Normalization, Normalize};
+use crate::ordering::Ordering;
+#[cfg(feature = "rayon")]
+use crate::parallel::fwht_slice_par;
 use std::ops::{Add, Sub};
 
 /// Apply FWHT in-place to any container that can provide a mutable slice
@@ -26,11 +33,11 @@ use std::ops::{Add, Sub};
 ///
 /// # Errors
 ///
-/// Returns an error if the container length is not a power of 2.
-pub fn fwht_mut<T, V>(data: &mut T) -> Result<(), &'static str>
+/// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2.
+pub fn fwht_mut<T, V>(data: &mut T) -> Result<(), FwhtError>
 where
     T: AsMut<[V]> + ?Sized,
-    V: Add<Output = V> + Sub<Output = V> + Copy,
+    V: Add<Output = V> + Sub<Output = V> + Copy + 'static,
 {
     fwht_slice(data.as_mut())
 }
@@ -56,17 +63,212 @@ where
 ///
 /// # Errors
 ///
-/// Returns an error if the container length is not a power of 2.
-pub fn fwht<T, V>(data: &T) -> Result<T, &'static str>
+/// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2.
+pub fn fwht<T, V>(data: &T) -> Result<T, FwhtError>
 where
     T: Clone + AsMut<[V]>,
-    V: Add<Output = V> + Sub<Output = V> + Copy,
+    V: Add<Output = V> + Sub<Output = V> + Copy + 'static,
 {
     let mut result = data.clone();
     fwht_mut(&mut result)?;
     Ok(result)
 }
 
+/// Write the FWHT of any container that can provide a slice into `dst`
+///
+/// The source is left unchanged and nothing is allocated; see
+/// [`fwht_slice_into`] for how the copy is fused into the transform.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::fwht_into;
+///
+/// let data = vec![1.0, 1.0, 1.0, 0.0];
+/// let mut out = [0.0; 4];
+/// fwht_into(&data, &mut out).unwrap();
+/// assert_eq!(out, [3.0, 1.0, 1.0, -1.0]);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the source length is not a power of 2,
+/// and [`FwhtError::LengthMismatch`] if `dst` has a different length.
+pub fn fwht_into<T, V>(data: &T, dst: &mut [V]) -> Result<(), FwhtError>
+where
+    T: AsRef<[V]> + ?Sized,
+    V: Add<Output = V> + Sub<Output = V> + Copy + 'static,
+{
+    fwht_slice_into(data.as_ref(), dst)
+}
+
+/// Apply FWHT in-place with the given normalization
+///
+/// # Examples
+///
+/// ```
+/// use fwht::{fwht_mut_normalized, Normalization};
+///
+/// let mut data = vec![1.0, 1.0, 1.0, 0.0];
+/// fwht_mut_normalized(&mut data, Normalization::ByN).unwrap();
+/// assert_eq!(data, vec![0.75, 0.25, 0.25, -0.25]);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2,
+/// and [`FwhtError::UnsupportedNormalization`] if the scale factor cannot be
+/// represented in `V`.
+pub fn fwht_mut_normalized<T, V>(
+    data: &mut T,
+    normalization: Normalization,
+) -> Result<(), FwhtError>
+where
+    T: AsMut<[V]> + ?Sized,
+    V: Add<Output = V> + Sub<Output = V> + Normalize,
+{
+    fwht_slice_normalized(data.as_mut(), normalization)
+}
+
+/// Apply FWHT with the given normalization and return a new container
+///
+/// # Errors
+///
+/// Same as [`fwht_mut_normalized`].
+pub fn fwht_normalized<T, V>(data: &T, normalization: Normalization) -> Result<T, FwhtError>
+where
+    T: Clone + AsMut<[V]>,
+    V: Add<Output = V> + Sub<Output = V> + Normalize,
+{
+    let mut result = data.clone();
+    fwht_mut_normalized(&mut result, normalization)?;
+    Ok(result)
+}
+
+/// Apply the inverse FWHT in-place
+///
+/// Exactly inverts [`fwht_mut_normalized`] called with the same `normalization`.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::{fwht_mut, ifwht_mut, Normalization};
+///
+/// let mut data = vec![1.0, 2.0, 3.0, 4.0];
+/// fwht_mut(&mut data).unwrap();
+/// ifwht_mut(&mut data, Normalization::None).unwrap();
+/// assert_eq!(data, vec![1.0, 2.0, 3.0, 4.0]);
+/// ```
+///
+/// # Errors
+///
+/// Same as [`fwht_mut_normalized`].
+pub fn ifwht_mut<T, V>(data: &mut T, normalization: Normalization) -> Result<(), FwhtError>
+where
+    T: AsMut<[V]> + ?Sized,
+    V: Add<Output = V> + Sub<Output = V> + Normalize,
+{
+    ifwht_slice(data.as_mut(), normalization)
+}
+
+/// Apply the inverse FWHT and return a new container with the result
+///
+/// # Errors
+///
+/// Same as [`fwht_mut_normalized`].
+pub fn ifwht<T, V>(data: &T, normalization: Normalization) -> Result<T, FwhtError>
+where
+    T: Clone + AsMut<[V]>,
+    V: Add<Output = V> + Sub<Output = V> + Normalize,
+{
+    let mut result = data.clone();
+    ifwht_mut(&mut result, normalization)?;
+    Ok(result)
+}
+
+/// Apply FWHT in-place with the coefficients in the given ordering
+///
+/// # Examples
+///
+/// ```
+/// use fwht::{fwht_mut_ordered, Ordering};
+///
+/// let mut data = vec![1.0, 2.0, 3.0, 4.0];
+/// fwht_mut_ordered(&mut data, Ordering::Dyadic).unwrap();
+/// assert_eq!(data, vec![10.0, -4.0, -2.0, 0.0]);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2.
+pub fn fwht_mut_ordered<T, V>(data: &mut T, ordering: Ordering) -> Result<(), FwhtError>
+where
+    T: AsMut<[V]> + ?Sized,
+    V: Add<Output = V> + Sub<Output = V> + Copy + 'static,
+{
+    fwht_slice_ordered(data.as_mut(), ordering)
+}
+
+/// Apply FWHT with the given ordering and return a new container
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2.
+pub fn fwht_ordered<T, V>(data: &T, ordering: Ordering) -> Result<T, FwhtError>
+where
+    T: Clone + AsMut<[V]>,
+    V: Add<Output = V> + Sub<Output = V> + Copy + 'static,
+{
+    let mut result = data.clone();
+    fwht_mut_ordered(&mut result, ordering)?;
+    Ok(result)
+}
+
+/// Apply FWHT in-place using multiple threads
+///
+/// Produces exactly the same result as [`fwht_mut`]. Requires the "rayon"
+/// feature.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::fwht_par_mut;
+///
+/// let mut data = vec![1.0, 1.0, 1.0, 0.0];
+/// fwht_par_mut(&mut data).unwrap();
+/// assert_eq!(data, vec![3.0, 1.0, 1.0, -1.0]);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2.
+#[cfg(feature = "rayon")]
+pub fn fwht_par_mut<T, V>(data: &mut T) -> Result<(), FwhtError>
+where
+    T: AsMut<[V]> + ?Sized,
+    V: Add<Output = V> + Sub<Output = V> + Copy + Send + Sync,
+{
+    fwht_slice_par(data.as_mut())
+}
+
+/// Apply FWHT using multiple threads and return a new container
+///
+/// Requires the "rayon" feature.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2.
+#[cfg(feature = "rayon")]
+pub fn fwht_par<T, V>(data: &T) -> Result<T, FwhtError>
+where
+    T: Clone + AsMut<[V]>,
+    V: Add<Output = V> + Sub<Output = V> + Copy + Send + Sync,
+{
+    let mut result = data.clone();
+    fwht_par_mut(&mut result)?;
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,7 +340,7 @@ mod tests {
         let mut data = vec![1.0, 2.0, 3.0];
         let result = fwht_mut(&mut data);
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Input length must be a power of 2");
+        assert_eq!(result.unwrap_err(), FwhtError::NotPowerOfTwo { len: 3 });
     }
 
     #[test]
@@ -154,6 +356,60 @@ mod tests {
         assert_eq!(vec_result, array_result.to_vec());
     }
 
+    #[test]
+    fn test_fwht_normalized_orthonormal() {
+        let data = vec![1.0, 1.0, 1.0, 0.0];
+        let result = fwht_normalized(&data, Normalization::Orthonormal).unwrap();
+        assert_eq!(result, vec![1.5, 0.5, 0.5, -0.5]);
+    }
+
+    #[test]
+    fn test_ifwht_round_trip() {
+        let data = [3.0f64, -1.0, 4.0, 1.0, -5.0, 9.0, 2.0, 6.0];
+        for norm in [
+            Normalization::None,
+            Normalization::Orthonormal,
+            Normalization::ByN,
+        ] {
+            let spectrum = fwht_normalized(&data, norm).unwrap();
+            let restored = ifwht(&spectrum, norm).unwrap();
+            for (actual, expected) in restored.iter().zip(data.iter()) {
+                assert!((actual - expected).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ifwht_mut_integers() {
+        let mut data = vec![1i32, 2i32, 3i32, 4i32];
+        fwht_mut(&mut data).unwrap();
+        ifwht_mut(&mut data, Normalization::None).unwrap();
+        assert_eq!(data, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_fwht_ordered_matches_reorder() {
+        let data = vec![3.0, -1.0, 4.0, 1.0, -5.0, 9.0, 2.0, 6.0];
+        let natural = fwht(&data).unwrap();
+
+        for ordering in [Ordering::Sequency, Ordering::Dyadic] {
+            let mut expected = natural.clone();
+            crate::ordering::reorder(&mut expected, Ordering::Natural, ordering).unwrap();
+            assert_eq!(fwht_ordered(&data, ordering).unwrap(), expected);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_fwht_par_matches_serial() {
+        let data: Vec<f64> = (0..1 << 16).map(|i| (i % 9) as f64).collect();
+        assert_eq!(fwht_par(&data).unwrap(), fwht(&data).unwrap());
+
+        let mut array = [1.0, 1.0, 1.0, 0.0];
+        fwht_par_mut(&mut array).unwrap();
+        assert_eq!(array, [3.0, 1.0, 1.0, -1.0]);
+    }
+
     #[test]
     fn test_api_consistency_with_trait() {
         use crate::traits::FWHT;
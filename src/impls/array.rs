@@ -3,7 +3,12 @@
 //! This module provides the Fast Walsh-Hadamard Transform implementation
 //! for static arrays of fixed size.
 
-use crate::core::fwht_slice;
+use crate::core::{fwht_slice, fwht_slice_into, fwht_slice_normalized, fwht_slice_ordered};
+use crate::error::FwhtError;
+use crate::normalize::{Normalization, Normalize};
+use crate::ordering::Ordering;
+#[cfg(feature = "rayon")]
+use crate::parallel::fwht_slice_par;
 use crate::traits::FWHT;
 use std::ops::{Add, Sub};
 
@@ -28,17 +33,48 @@ use std::ops::{Add, Sub};
 /// ```
 impl<T, const N: usize> FWHT<T> for [T; N]
 where
-    T: Add<Output = T> + Sub<Output = T> + Copy,
+    T: Add<Output = T> + Sub<Output = T> + Copy + 'static,
 {
-    fn fwht_mut(&mut self) -> Result<(), &'static str> {
+    fn fwht_mut(&mut self) -> Result<(), FwhtError> {
         fwht_slice(self.as_mut_slice())
     }
 
-    fn fwht(&self) -> Result<Self, &'static str> {
+    fn fwht(&self) -> Result<Self, FwhtError> {
         let mut result = *self;
         result.fwht_mut()?;
         Ok(result)
     }
+
+    fn as_fwht_slice(&self) -> Option<&[T]> {
+        Some(self.as_slice())
+    }
+
+    fn as_fwht_slice_mut(&mut self) -> Option<&mut [T]> {
+        Some(self.as_mut_slice())
+    }
+
+    fn fwht_mut_normalized(&mut self, normalization: Normalization) -> Result<(), FwhtError>
+    where
+        T: Normalize,
+    {
+        fwht_slice_normalized(self.as_mut_slice(), normalization)
+    }
+
+    fn fwht_into(&self, dst: &mut [T]) -> Result<(), FwhtError> {
+        fwht_slice_into(self, dst)
+    }
+
+    fn fwht_mut_ordered(&mut self, ordering: Ordering) -> Result<(), FwhtError> {
+        fwht_slice_ordered(self.as_mut_slice(), ordering)
+    }
+
+    #[cfg(feature = "rayon")]
+    fn fwht_par_mut(&mut self) -> Result<(), FwhtError>
+    where
+        T: Send + Sync,
+    {
+        fwht_slice_par(self.as_mut_slice())
+    }
 }
 
 #[cfg(test)]
@@ -60,6 +96,14 @@ mod tests {
         assert_eq!(data, [1f64, 1f64, 1f64, 0f64]);
     }
 
+    #[test]
+    fn test_array_fwht_into() {
+        let data = [1i32, 2, 3, 4];
+        let mut out = [0i32; 4];
+        data.fwht_into(&mut out).unwrap();
+        assert_eq!(out, [10, -2, -4, 0]);
+    }
+
     #[test]
     fn test_array_fwht_size_8() {
         let mut data = [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
@@ -1,17 +1,33 @@
-//! FWHT implementation for ndarray::Array1<T>
+//! FWHT implementation for ndarray arrays
 //!
 //! This module provides the Fast Walsh-Hadamard Transform implementation
-//! for `ndarray::Array1<T>` containers when the "ndarray" feature is enabled.
+//! for one-dimensional ndarray arrays and views of any stride, and transforms
+//! along the axes of arrays of any dimension, when the "ndarray" feature is
+//! enabled.
 
-use crate::core::fwht_slice;
-use crate::traits::FWHT;
+use crate::core::{
+    fwht_slice, fwht_slice_into, fwht_slice_normalized, fwht_slice_ordered, fwht_unchecked,
+    validate_length,
+};
+use crate::error::FwhtError;
+use crate::normalize::{Normalization, Normalize};
+use crate::ordering::Ordering;
+#[cfg(feature = "rayon")]
+use crate::parallel::fwht_slice_par;
+use crate::traits::{FWHTAxis, FWHT};
+use ndarray::{Array, ArrayBase, ArrayView1, ArrayViewMut1, Axis, DataMut, Dimension, Ix1};
 use std::ops::{Add, Sub};
 
-/// Implementation of FWHT for ndarray::Array1<T>
+/// Implementation of FWHT for one-dimensional ndarray arrays and views
 ///
-/// This implementation works with `ndarray::Array1<T>` where `T` implements
-/// the required arithmetic operations. The array must be contiguous in memory
-/// for the transform to work.
+/// This implementation works with any `ArrayBase<S, Ix1>` with mutable
+/// storage: `Array1<T>`, `ArrayViewMut1<T>`, `ArcArray1<T>` and `CowArray<T, Ix1>`,
+/// where `T` implements the required arithmetic operations.
+///
+/// Contiguous arrays are transformed in place. Arrays with any other stride
+/// (sliced views, columns of a 2-D array, reversed axes) are gathered into a
+/// temporary buffer, transformed and scattered back, so the result is the
+/// same as transforming the elements in logical order.
 ///
 /// # Examples
 ///
@@ -19,7 +35,7 @@ use std::ops::{Add, Sub};
 /// # #[cfg(feature = "ndarray")]
 /// # {
 /// use fwht::FWHT;
-/// use ndarray::Array1;
+/// use ndarray::{s, Array1, Array2};
 ///
 /// let mut data = Array1::from(vec![1.0, 1.0, 1.0, 0.0]);
 /// data.fwht_mut().unwrap();
@@ -30,36 +46,226 @@ use std::ops::{Add, Sub};
 /// let result = data2.fwht().unwrap();
 /// let expected = Array1::from(vec![3.0, 1.0, 1.0, -1.0]);
 /// assert_eq!(result, expected);
+///
+/// // Strided views are transformed in place
+/// let mut interleaved = Array1::from(vec![1.0, 9.0, 1.0, 9.0, 1.0, 9.0, 0.0, 9.0]);
+/// interleaved.slice_mut(s![..;2]).fwht_mut().unwrap();
+/// assert_eq!(interleaved, Array1::from(vec![3.0, 9.0, 1.0, 9.0, 1.0, 9.0, -1.0, 9.0]));
+///
+/// let mut matrix = Array2::<f64>::ones((4, 3));
+/// matrix.column_mut(1).fwht_mut().unwrap();
+/// assert_eq!(matrix.column(1).to_vec(), vec![4.0, 0.0, 0.0, 0.0]);
 /// # }
 /// ```
 ///
-/// # Panics
+/// # Errors
 ///
-/// Panics if the array is not contiguous in memory.
-#[cfg(feature = "ndarray")]
-impl<T> FWHT<T> for ndarray::Array1<T>
+/// Returns [`FwhtError::NotPowerOfTwo`] if the array length is not a power of 2.
+impl<S, T> FWHT<T> for ArrayBase<S, Ix1>
 where
-    T: Add<Output = T> + Sub<Output = T> + Copy + Clone,
+    S: DataMut<Elem = T>,
+    T: Add<Output = T> + Sub<Output = T> + Copy + 'static,
 {
-    fn fwht_mut(&mut self) -> Result<(), &'static str> {
-        if let Some(slice) = self.as_slice_mut() {
-            fwht_slice(slice)
-        } else {
-            Err("Array must be contiguous for FWHT")
+    fn fwht_mut(&mut self) -> Result<(), FwhtError> {
+        with_lane_slice(self.view_mut(), &mut Vec::new(), fwht_slice)
+    }
+
+    fn as_fwht_slice(&self) -> Option<&[T]> {
+        self.as_slice()
+    }
+
+    fn as_fwht_slice_mut(&mut self) -> Option<&mut [T]> {
+        self.as_slice_mut()
+    }
+
+    fn fwht_mut_normalized(&mut self, normalization: Normalization) -> Result<(), FwhtError>
+    where
+        T: Normalize,
+    {
+        with_lane_slice(self.view_mut(), &mut Vec::new(), |slice| {
+            fwht_slice_normalized(slice, normalization)
+        })
+    }
+
+    fn fwht_into(&self, dst: &mut [T]) -> Result<(), FwhtError> {
+        fwht_view_into(self.view(), dst)
+    }
+
+    fn fwht_mut_ordered(&mut self, ordering: Ordering) -> Result<(), FwhtError> {
+        with_lane_slice(self.view_mut(), &mut Vec::new(), |slice| {
+            fwht_slice_ordered(slice, ordering)
+        })
+    }
+
+    #[cfg(feature = "rayon")]
+    fn fwht_par_mut(&mut self) -> Result<(), FwhtError>
+    where
+        T: Send + Sync,
+    {
+        with_lane_slice(self.view_mut(), &mut Vec::new(), fwht_slice_par)
+    }
+}
+
+/// Writes the FWHT of a one-dimensional view into `dst`
+///
+/// Same as [`FWHT::fwht_into`], but takes an [`ArrayView1`], so it works on
+/// arrays the caller can only borrow immutably. Views of any stride are
+/// accepted; contiguous ones are read directly by the fused copy of
+/// [`fwht_slice_into`].
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the view length is not a power
+/// of 2, and [`FwhtError::LengthMismatch`] if `dst` has a different length.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "ndarray")]
+/// # {
+/// use fwht::fwht_view_into;
+/// use ndarray::{s, Array1};
+///
+/// let data = Array1::from(vec![1.0, 9.0, 1.0, 9.0, 1.0, 9.0, 0.0, 9.0]);
+/// let mut out = [0.0; 4];
+/// fwht_view_into(data.slice(s![..;2]), &mut out).unwrap();
+/// assert_eq!(out, [3.0, 1.0, 1.0, -1.0]);
+/// # }
+/// ```
+pub fn fwht_view_into<T>(src: ArrayView1<'_, T>, dst: &mut [T]) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    if let Some(src) = src.as_slice() {
+        return fwht_slice_into(src, dst);
+    }
+
+    validate_length(src.len())?;
+    if dst.len() != src.len() {
+        return Err(FwhtError::LengthMismatch {
+            expected: src.len(),
+            got: dst.len(),
+        });
+    }
+    for (d, &s) in dst.iter_mut().zip(src.iter()) {
+        *d = s;
+    }
+    fwht_unchecked(dst);
+    Ok(())
+}
+
+/// Implementation of FWHT along axes for n-dimensional arrays
+///
+/// Works with any owned array or mutable view (`Array2`, `Array3`, `ArrayD`,
+/// `ArrayViewMut2`, ...). Lanes that are contiguous in memory are transformed
+/// directly; strided lanes are gathered into a scratch buffer that is reused
+/// across lanes.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "ndarray")]
+/// # {
+/// use fwht::FWHTAxis;
+/// use ndarray::Array3;
+///
+/// let mut data = Array3::<f64>::ones((2, 4, 8));
+/// data.fwht_all_axes_mut().unwrap();
+/// assert_eq!(data[[0, 0, 0]], 64.0);
+/// assert_eq!(data.sum(), 64.0);
+/// # }
+/// ```
+impl<S, D, T> FWHTAxis<T> for ArrayBase<S, D>
+where
+    S: DataMut<Elem = T>,
+    D: Dimension,
+    T: Add<Output = T> + Sub<Output = T> + Copy,
+{
+    type Output = Array<T, D>;
+
+    fn fwht_axis_mut(&mut self, axis: usize) -> Result<(), FwhtError> {
+        self.fwht_axes_mut(&[axis])
+    }
+
+    fn fwht_axes_mut(&mut self, axes: &[usize]) -> Result<(), FwhtError> {
+        for &axis in axes {
+            if axis >= self.ndim() {
+                return Err(FwhtError::InvalidAxis {
+                    axis,
+                    ndim: self.ndim(),
+                });
+            }
+            validate_length(self.len_of(Axis(axis)))?;
+        }
+
+        let mut buffer = Vec::new();
+        for &axis in axes {
+            for lane in self.lanes_mut(Axis(axis)) {
+                with_lane_slice(lane, &mut buffer, |slice| {
+                    fwht_unchecked(slice);
+                    Ok(())
+                })?;
+            }
         }
+
+        Ok(())
+    }
+
+    fn fwht_all_axes_mut(&mut self) -> Result<(), FwhtError> {
+        let axes: Vec<usize> = (0..self.ndim()).collect();
+        self.fwht_axes_mut(&axes)
+    }
+
+    fn fwht_axis(&self, axis: usize) -> Result<Self::Output, FwhtError> {
+        let mut result = self.to_owned();
+        result.fwht_axis_mut(axis)?;
+        Ok(result)
     }
 
-    fn fwht(&self) -> Result<Self, &'static str> {
-        let mut result = self.clone();
-        result.fwht_mut()?;
+    fn fwht_axes(&self, axes: &[usize]) -> Result<Self::Output, FwhtError> {
+        let mut result = self.to_owned();
+        result.fwht_axes_mut(axes)?;
+        Ok(result)
+    }
+
+    fn fwht_all_axes(&self) -> Result<Self::Output, FwhtError> {
+        let mut result = self.to_owned();
+        result.fwht_all_axes_mut()?;
         Ok(result)
     }
 }
 
+/// Runs `f` on the elements of a lane as a contiguous slice
+///
+/// Contiguous lanes are passed through directly. Strided lanes are gathered
+/// into `buffer` in logical order, and scattered back only if `f` succeeds.
+fn with_lane_slice<T, F>(
+    mut lane: ArrayViewMut1<'_, T>,
+    buffer: &mut Vec<T>,
+    f: F,
+) -> Result<(), FwhtError>
+where
+    T: Copy,
+    F: FnOnce(&mut [T]) -> Result<(), FwhtError>,
+{
+    if let Some(slice) = lane.as_slice_mut() {
+        return f(slice);
+    }
+
+    buffer.clear();
+    buffer.extend(lane.iter().copied());
+    f(buffer)?;
+    for (dst, &src) in lane.iter_mut().zip(buffer.iter()) {
+        *dst = src;
+    }
+
+    Ok(())
+}
+
 #[cfg(all(test, feature = "ndarray"))]
 mod tests {
     use super::*;
-    use ndarray::Array1;
+    use ndarray::{array, s, ArcArray1, Array1, Array2, Array3, CowArray};
 
     #[test]
     fn test_ndarray_fwht_mut() {
@@ -80,6 +286,34 @@ mod tests {
         assert_eq!(data, original_expected);
     }
 
+    #[test]
+    fn test_ndarray_fwht_into() {
+        let data = Array1::from(vec![1.0, 1.0, 1.0, 0.0]);
+        let mut out = [0.0; 4];
+        data.fwht_into(&mut out).unwrap();
+        assert_eq!(out, [3.0, 1.0, 1.0, -1.0]);
+
+        // Strided and reversed immutable views are gathered in logical order
+        let interleaved = Array1::from(vec![1.0, 9.0, 1.0, 9.0, 1.0, 9.0, 0.0, 9.0]);
+        let shared = &interleaved;
+        fwht_view_into(shared.slice(s![..;2]), &mut out).unwrap();
+        assert_eq!(out, [3.0, 1.0, 1.0, -1.0]);
+        fwht_view_into(shared.slice(s![..7;-2]), &mut out).unwrap();
+        assert_eq!(out, [3.0, -1.0, -1.0, -1.0]);
+        fwht_view_into(data.view(), &mut out).unwrap();
+        assert_eq!(out, [3.0, 1.0, 1.0, -1.0]);
+        assert_eq!(interleaved[0], 1.0);
+
+        let mut short = [0.0; 2];
+        assert_eq!(
+            data.fwht_into(&mut short),
+            Err(FwhtError::LengthMismatch {
+                expected: 4,
+                got: 2
+            })
+        );
+    }
+
     #[test]
     fn test_ndarray_fwht_size_8() {
         let mut data = Array1::from(vec![1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
@@ -137,6 +371,83 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_ndarray_fwht_non_contiguous() {
+        let mut data = Array1::from(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+        data.slice_collapse(s![..;2]);
+        data.fwht_mut().unwrap();
+        assert_eq!(data, Array1::from(vec![16.0, -4.0, -8.0, 0.0]));
+    }
+
+    #[test]
+    fn test_ndarray_view_mut_strided() {
+        let mut data = Array1::from(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+        data.slice_mut(s![1..;2]).fwht_mut().unwrap();
+        assert_eq!(
+            data,
+            Array1::from(vec![1.0, 20.0, 3.0, -4.0, 5.0, -8.0, 7.0, 0.0])
+        );
+    }
+
+    #[test]
+    fn test_ndarray_negative_stride() {
+        let mut data = Array1::from(vec![1.0, 2.0, 3.0, 4.0]);
+        data.slice_mut(s![..;-1]).fwht_mut().unwrap();
+
+        let mut expected = vec![4.0, 3.0, 2.0, 1.0];
+        fwht_slice(&mut expected).unwrap();
+        expected.reverse();
+        assert_eq!(data, Array1::from(expected));
+    }
+
+    #[test]
+    fn test_ndarray_column_of_matrix() {
+        let mut matrix = Array2::from_shape_fn((4, 2), |(i, j)| (i * 2 + j) as f64);
+        matrix
+            .column_mut(0)
+            .fwht_mut_normalized(Normalization::ByN)
+            .unwrap();
+        assert_eq!(matrix.column(0), array![3.0, -1.0, -2.0, 0.0]);
+        assert_eq!(matrix.column(1), array![1.0, 3.0, 5.0, 7.0]);
+    }
+
+    #[test]
+    fn test_ndarray_strided_ordered() {
+        let mut matrix = Array2::from_shape_fn((8, 2), |(i, _)| i as f64);
+        matrix
+            .column_mut(1)
+            .fwht_mut_ordered(Ordering::Sequency)
+            .unwrap();
+
+        let mut expected: Vec<f64> = (0..8).map(|i| i as f64).collect();
+        fwht_slice_ordered(&mut expected, Ordering::Sequency).unwrap();
+        assert_eq!(matrix.column(1).to_vec(), expected);
+    }
+
+    #[test]
+    fn test_ndarray_strided_error_leaves_data() {
+        let mut data = Array1::from(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let result = data.slice_mut(s![..;2]).fwht_mut();
+        assert_eq!(result, Err(FwhtError::NotPowerOfTwo { len: 3 }));
+        assert_eq!(data, Array1::from(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]));
+    }
+
+    #[test]
+    fn test_ndarray_other_storages() {
+        let expected = Array1::from(vec![3.0, 1.0, 1.0, -1.0]);
+
+        let arc: ArcArray1<f64> = ArcArray1::from(vec![1.0, 1.0, 1.0, 0.0]);
+        let shared = arc.clone();
+        assert_eq!(arc.fwht().unwrap(), expected);
+        assert_eq!(shared, Array1::from(vec![1.0, 1.0, 1.0, 0.0]));
+
+        let source = Array1::from(vec![1.0, 1.0, 1.0, 0.0]);
+        let mut cow = CowArray::from(source.view());
+        cow.fwht_mut().unwrap();
+        assert_eq!(cow, expected);
+        assert_eq!(source, Array1::from(vec![1.0, 1.0, 1.0, 0.0]));
+    }
+
     #[test]
     fn test_ndarray_fwht_involution() {
         let original = Array1::from(vec![1.0, 2.0, 3.0, 4.0]);
@@ -188,4 +499,89 @@ mod tests {
         ]);
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_axis_transform_2d() {
+        let mut data = array![[1.0, 2.0, 3.0, 4.0], [0.0, 1.0, 0.0, 1.0]];
+        data.fwht_axis_mut(1).unwrap();
+        assert_eq!(data, array![[10.0, -2.0, -4.0, 0.0], [2.0, -2.0, 0.0, 0.0]]);
+
+        data.fwht_axis_mut(0).unwrap();
+        assert_eq!(data, array![[12.0, -4.0, -4.0, 0.0], [8.0, 0.0, -4.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_all_axes_matches_separable_transform() {
+        let data = Array2::from_shape_fn((4, 8), |(i, j)| (i * 8 + j) as f64 * 0.5 - 3.0);
+
+        let mut expected = data.clone();
+        for mut row in expected.rows_mut() {
+            let mut values = row.to_vec();
+            fwht_slice(&mut values).unwrap();
+            row.assign(&Array1::from(values));
+        }
+        for mut column in expected.columns_mut() {
+            let mut values = column.to_vec();
+            fwht_slice(&mut values).unwrap();
+            column.assign(&Array1::from(values));
+        }
+
+        assert_eq!(data.fwht_all_axes().unwrap(), expected);
+        assert_eq!(data.fwht_axes(&[1, 0]).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_axis_transform_3d_and_dyn() {
+        let data = Array3::from_shape_fn((2, 4, 2), |(i, j, k)| (i + 2 * j + 3 * k) as i64);
+        let dynamic = data.clone().into_dyn();
+
+        let result = data.fwht_axis(1).unwrap();
+        let result_dyn = dynamic.fwht_axis(1).unwrap();
+        assert_eq!(result.into_dyn(), result_dyn);
+
+        for i in 0..2 {
+            for k in 0..2 {
+                let mut lane: Vec<i64> = (0..4).map(|j| data[[i, j, k]]).collect();
+                fwht_slice(&mut lane).unwrap();
+                let actual: Vec<i64> = (0..4).map(|j| result_dyn[[i, j, k]]).collect();
+                assert_eq!(actual, lane);
+            }
+        }
+    }
+
+    #[test]
+    fn test_axis_transform_on_view() {
+        let mut data = Array2::<f64>::ones((4, 3));
+        data.column_mut(1).fill(2.0);
+        let mut view = data.view_mut();
+        view.fwht_axis_mut(0).unwrap();
+        assert_eq!(data.row(0), array![4.0, 8.0, 4.0]);
+        assert_eq!(data.row(1), array![0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_axis_errors() {
+        let mut data = Array2::<f64>::zeros((3, 4));
+        assert_eq!(
+            data.fwht_axis_mut(2),
+            Err(FwhtError::InvalidAxis { axis: 2, ndim: 2 })
+        );
+        assert_eq!(
+            data.fwht_axis_mut(0),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+        assert!(data.fwht_axis_mut(1).is_ok());
+        assert_eq!(
+            data.fwht_all_axes_mut(),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+    }
+
+    #[test]
+    fn test_axes_validation_is_atomic() {
+        let mut data = Array2::from_shape_fn((4, 3), |(i, j)| (i + j) as f64);
+        let original = data.clone();
+        assert!(data.fwht_axes_mut(&[0, 1]).is_err());
+        assert_eq!(data, original);
+    }
 }
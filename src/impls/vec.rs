@@ -3,7 +3,12 @@
 //! This module provides the Fast Walsh-Hadamard Transform implementation
 //! for `Vec<T>` containers.
 
-use crate::core::fwht_slice;
+use crate::core::{fwht_slice, fwht_slice_into, fwht_slice_normalized, fwht_slice_ordered};
+use crate::error::FwhtError;
+use crate::normalize::{Normalization, Normalize};
+use crate::ordering::Ordering;
+#[cfg(feature = "rayon")]
+use crate::parallel::fwht_slice_par;
 use crate::traits::FWHT;
 use std::ops::{Add, Sub};
 
@@ -27,17 +32,48 @@ use std::ops::{Add, Sub};
 /// ```
 impl<T> FWHT<T> for Vec<T>
 where
-    T: Add<Output = T> + Sub<Output = T> + Copy + Clone,
+    T: Add<Output = T> + Sub<Output = T> + Copy + Clone + 'static,
 {
-    fn fwht_mut(&mut self) -> Result<(), &'static str> {
+    fn fwht_mut(&mut self) -> Result<(), FwhtError> {
         fwht_slice(self.as_mut_slice())
     }
 
-    fn fwht(&self) -> Result<Self, &'static str> {
+    fn fwht(&self) -> Result<Self, FwhtError> {
         let mut result = self.clone();
         result.fwht_mut()?;
         Ok(result)
     }
+
+    fn as_fwht_slice(&self) -> Option<&[T]> {
+        Some(self.as_slice())
+    }
+
+    fn as_fwht_slice_mut(&mut self) -> Option<&mut [T]> {
+        Some(self.as_mut_slice())
+    }
+
+    fn fwht_mut_normalized(&mut self, normalization: Normalization) -> Result<(), FwhtError>
+    where
+        T: Normalize,
+    {
+        fwht_slice_normalized(self.as_mut_slice(), normalization)
+    }
+
+    fn fwht_into(&self, dst: &mut [T]) -> Result<(), FwhtError> {
+        fwht_slice_into(self, dst)
+    }
+
+    fn fwht_mut_ordered(&mut self, ordering: Ordering) -> Result<(), FwhtError> {
+        fwht_slice_ordered(self.as_mut_slice(), ordering)
+    }
+
+    #[cfg(feature = "rayon")]
+    fn fwht_par_mut(&mut self) -> Result<(), FwhtError>
+    where
+        T: Send + Sync,
+    {
+        fwht_slice_par(self.as_mut_slice())
+    }
 }
 
 #[cfg(test)]
@@ -59,6 +95,24 @@ mod tests {
         assert_eq!(data, vec![1f64, 1f64, 1f64, 0f64]);
     }
 
+    #[test]
+    fn test_vec_fwht_into() {
+        let data = vec![1f64, 1f64, 1f64, 0f64];
+        let mut out = vec![0f64; 4];
+        data.fwht_into(&mut out).unwrap();
+        assert_eq!(out, vec![3f64, 1f64, 1f64, -1f64]);
+        assert_eq!(data, vec![1f64, 1f64, 1f64, 0f64]);
+
+        let mut short = vec![0f64; 2];
+        assert_eq!(
+            data.fwht_into(&mut short),
+            Err(FwhtError::LengthMismatch {
+                expected: 4,
+                got: 2
+            })
+        );
+    }
+
     #[test]
     fn test_vec_fwht_size_8() {
         let mut data = vec![1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
@@ -102,7 +156,7 @@ mod tests {
         let mut data = vec![1.0, 2.0, 3.0];
         let result = data.fwht_mut();
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Input length must be a power of 2");
+        assert_eq!(result.unwrap_err(), FwhtError::NotPowerOfTwo { len: 3 });
     }
 
     #[test]
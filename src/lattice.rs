@@ -0,0 +1,473 @@
+//! Zeta and Möbius transforms on the Boolean lattice
+//!
+//! Indices of a slice of length `2^m` are read as subsets of `m` elements.
+//! The subset zeta transform sums each entry over all of its subsets, and the
+//! superset zeta transform over all of its supersets:
+//!
+//! ```text
+//! subset_zeta(f)[S]   = Σ f[T]  over all T ⊆ S
+//! superset_zeta(f)[S] = Σ f[T]  over all T ⊇ S
+//! ```
+//!
+//! The Möbius transforms are their exact inverses. All four share the loop
+//! structure of the Walsh-Hadamard transform; only the butterfly differs.
+//! Each butterfly only adds or subtracts, so the results are exact for
+//! integers and [`ModInt`](crate::modular::ModInt).
+//!
+//! Just as the Walsh-Hadamard transform diagonalizes XOR convolution, the
+//! subset zeta transform diagonalizes OR convolution and the superset zeta
+//! transform diagonalizes AND convolution, giving [`or_convolve`] and
+//! [`and_convolve`] in `O(n log n)`.
+//!
+//! Subset convolution, which only combines disjoint pairs, is computed with
+//! ranked transforms in [`subset_convolve`].
+//!
+//! # Examples
+//!
+//! ```
+//! use fwht::lattice::{subset_mobius, subset_zeta};
+//!
+//! let mut data = [1, 2, 3, 4];
+//! subset_zeta(&mut data).unwrap();
+//! assert_eq!(data, [1, 3, 4, 10]);
+//!
+//! subset_mobius(&mut data).unwrap();
+//! assert_eq!(data, [1, 2, 3, 4]);
+//! ```
+
+use crate::convolution::{padded, padded_len};
+use crate::core::{pair_stages, validate_length};
+use crate::error::FwhtError;
+use std::ops::{Add, Mul, Sub};
+
+/// Subset zeta transform: `f[S] ← Σ f[T]` over all `T ⊆ S`
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::lattice::subset_zeta;
+///
+/// let mut data = [1, 1, 1, 1, 1, 1, 1, 1];
+/// subset_zeta(&mut data).unwrap();
+/// assert_eq!(data, [1, 2, 2, 4, 2, 4, 4, 8]);
+/// ```
+pub fn subset_zeta<T>(data: &mut [T]) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Copy,
+{
+    validate_length(data.len())?;
+    pair_stages(data, 1, data.len(), add_lo_to_hi);
+    Ok(())
+}
+
+/// Subset Möbius transform, the inverse of [`subset_zeta`]
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::lattice::subset_mobius;
+///
+/// let mut data = [1, 2, 2, 4];
+/// subset_mobius(&mut data).unwrap();
+/// assert_eq!(data, [1, 1, 1, 1]);
+/// ```
+pub fn subset_mobius<T>(data: &mut [T]) -> Result<(), FwhtError>
+where
+    T: Sub<Output = T> + Copy,
+{
+    validate_length(data.len())?;
+    pair_stages(data, 1, data.len(), sub_lo_from_hi);
+    Ok(())
+}
+
+/// Superset zeta transform: `f[S] ← Σ f[T]` over all `T ⊇ S`
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::lattice::superset_zeta;
+///
+/// let mut data = [1, 2, 3, 4];
+/// superset_zeta(&mut data).unwrap();
+/// assert_eq!(data, [10, 6, 7, 4]);
+/// ```
+pub fn superset_zeta<T>(data: &mut [T]) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Copy,
+{
+    validate_length(data.len())?;
+    pair_stages(data, 1, data.len(), add_hi_to_lo);
+    Ok(())
+}
+
+/// Superset Möbius transform, the inverse of [`superset_zeta`]
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::lattice::superset_mobius;
+///
+/// let mut data = [10, 6, 7, 4];
+/// superset_mobius(&mut data).unwrap();
+/// assert_eq!(data, [1, 2, 3, 4]);
+/// ```
+pub fn superset_mobius<T>(data: &mut [T]) -> Result<(), FwhtError>
+where
+    T: Sub<Output = T> + Copy,
+{
+    validate_length(data.len())?;
+    pair_stages(data, 1, data.len(), sub_hi_from_lo);
+    Ok(())
+}
+
+/// OR convolution `c[k] = Σ a[i]·b[j]` over all `i | j = k`
+///
+/// Inputs of different lengths are zero-padded to the smallest common power
+/// of 2, which is also the length of the result.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::Overflow`] if the padded length does not fit in
+/// `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::lattice::or_convolve;
+///
+/// let c = or_convolve(&[1, 2], &[3, 4, 5, 6]).unwrap();
+/// assert_eq!(c, vec![3, 4 + 6 + 8, 5, 6 + 10 + 12]);
+/// ```
+pub fn or_convolve<T>(a: &[T], b: &[T]) -> Result<Vec<T>, FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy + Default,
+{
+    lattice_convolve(a, b, add_lo_to_hi, sub_lo_from_hi)
+}
+
+/// AND convolution `c[k] = Σ a[i]·b[j]` over all `i & j = k`
+///
+/// Inputs of different lengths are zero-padded to the smallest common power
+/// of 2, which is also the length of the result.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::Overflow`] if the padded length does not fit in
+/// `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::lattice::and_convolve;
+///
+/// let c = and_convolve(&[1, 2], &[3, 4, 5, 6]).unwrap();
+/// assert_eq!(c, vec![3 + 4 + 5 + 6 + 6 + 10, 8 + 12, 0, 0]);
+/// ```
+pub fn and_convolve<T>(a: &[T], b: &[T]) -> Result<Vec<T>, FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy + Default,
+{
+    lattice_convolve(a, b, add_hi_to_lo, sub_hi_from_lo)
+}
+
+/// Subset convolution `c[S] = Σ a[T]·b[S \ T]` over all `T ⊆ S`
+///
+/// Unlike [`or_convolve`], only disjoint pairs of sets are combined. For
+/// `n = 2^m` this takes `O(m²·n)` time and `O(m·n)` memory with ranked
+/// transforms: each input is split into `m + 1` layers by set size
+/// (popcount), every layer is subset-zeta transformed, the layers are
+/// multiplied as polynomials in the rank, and the result is Möbius
+/// transformed back. Inputs of different lengths are zero-padded to the
+/// smallest common power of 2, which is also the length of the result.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::Overflow`] if the padded or ranked length does not
+/// fit in `usize`.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::lattice::subset_convolve;
+///
+/// // Ways to split each set into an ordered pair of disjoint parts
+/// let ones = [1u64; 8];
+/// let c = subset_convolve(&ones, &ones).unwrap();
+/// assert_eq!(c, vec![1, 2, 2, 4, 2, 4, 4, 8]);
+/// ```
+pub fn subset_convolve<T>(a: &[T], b: &[T]) -> Result<Vec<T>, FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Copy + Default,
+{
+    let n = padded_len(a.len().max(b.len()))?;
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    let ranks = n.trailing_zeros() as usize + 1;
+
+    let mut ranked_a = ranked_zeta(a, n, ranks)?;
+    let ranked_b = ranked_zeta(b, n, ranks)?;
+
+    // Multiply the rank polynomials of each set, highest rank first so every
+    // coefficient is read before it is overwritten
+    for (x, y) in ranked_a
+        .chunks_exact_mut(ranks)
+        .zip(ranked_b.chunks_exact(ranks))
+    {
+        for r in (0..ranks).rev() {
+            let mut sum = x[0] * y[r];
+            for i in 1..=r {
+                sum = sum + x[i] * y[r - i];
+            }
+            x[r] = sum;
+        }
+    }
+
+    let len = ranked_a.len();
+    pair_stages(&mut ranked_a, ranks, len, sub_lo_from_hi);
+    Ok((0..n)
+        .map(|s| ranked_a[s * ranks + s.count_ones() as usize])
+        .collect())
+}
+
+/// Splits `data` into layers by popcount and zeta transforms every layer
+///
+/// The `ranks` layers are interleaved: rank `r` of set `s` is stored at
+/// `s * ranks + r`. Each butterfly then adds two contiguous runs of `ranks`
+/// elements, transforming all layers in one pass over the data.
+fn ranked_zeta<T>(data: &[T], n: usize, ranks: usize) -> Result<Vec<T>, FwhtError>
+where
+    T: Add<Output = T> + Copy + Default,
+{
+    let len = n.checked_mul(ranks).ok_or(FwhtError::Overflow)?;
+    let mut ranked = vec![T::default(); len];
+    for (s, &x) in data.iter().enumerate() {
+        ranked[s * ranks + s.count_ones() as usize] = x;
+    }
+    pair_stages(&mut ranked, ranks, len, add_lo_to_hi);
+    Ok(ranked)
+}
+
+/// Convolution diagonalized by the transform with butterfly `forward`
+fn lattice_convolve<T>(
+    a: &[T],
+    b: &[T],
+    forward: fn(&mut [T], &mut [T]),
+    inverse: fn(&mut [T], &mut [T]),
+) -> Result<Vec<T>, FwhtError>
+where
+    T: Mul<Output = T> + Copy + Default,
+{
+    let n = padded_len(a.len().max(b.len()))?;
+    let mut a = padded(a, n);
+    let mut b = padded(b, n);
+    pair_stages(&mut a, 1, n, forward);
+    pair_stages(&mut b, 1, n, forward);
+    for (x, &y) in a.iter_mut().zip(&b) {
+        *x = *x * y;
+    }
+    pair_stages(&mut a, 1, n, inverse);
+    Ok(a)
+}
+
+/// Butterfly of the subset zeta transform
+fn add_lo_to_hi<T: Add<Output = T> + Copy>(lo: &mut [T], hi: &mut [T]) {
+    for (a, b) in lo.iter().zip(hi.iter_mut()) {
+        *b = *b + *a;
+    }
+}
+
+/// Butterfly of the subset Möbius transform
+fn sub_lo_from_hi<T: Sub<Output = T> + Copy>(lo: &mut [T], hi: &mut [T]) {
+    for (a, b) in lo.iter().zip(hi.iter_mut()) {
+        *b = *b - *a;
+    }
+}
+
+/// Butterfly of the superset zeta transform
+fn add_hi_to_lo<T: Add<Output = T> + Copy>(lo: &mut [T], hi: &mut [T]) {
+    for (a, b) in lo.iter_mut().zip(hi.iter()) {
+        *a = *a + *b;
+    }
+}
+
+/// Butterfly of the superset Möbius transform
+fn sub_hi_from_lo<T: Sub<Output = T> + Copy>(lo: &mut [T], hi: &mut [T]) {
+    for (a, b) in lo.iter_mut().zip(hi.iter()) {
+        *a = *a - *b;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modular::ModInt;
+
+    type Mint = ModInt<998_244_353>;
+
+    fn naive_zeta(data: &[i64], superset: bool) -> Vec<i64> {
+        (0..data.len())
+            .map(|s| {
+                (0..data.len())
+                    .filter(|&t| if superset { t & s == s } else { t & s == t })
+                    .map(|t| data[t])
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn naive_convolve(a: &[i64], b: &[i64], op: fn(usize, usize) -> usize) -> Vec<i64> {
+        let n = padded_len(a.len().max(b.len())).unwrap();
+        let (a, b) = (padded(a, n), padded(b, n));
+        let mut c = vec![0; n];
+        for i in 0..n {
+            for j in 0..n {
+                c[op(i, j)] += a[i] * b[j];
+            }
+        }
+        c
+    }
+
+    fn sample(n: usize) -> Vec<i64> {
+        (0..n as i64).map(|i| (i * 37) % 11 - 5).collect()
+    }
+
+    #[test]
+    fn test_zeta_matches_definition() {
+        for log2 in 0..7 {
+            let data = sample(1 << log2);
+
+            let mut subset = data.clone();
+            subset_zeta(&mut subset).unwrap();
+            assert_eq!(subset, naive_zeta(&data, false));
+
+            let mut superset = data.clone();
+            superset_zeta(&mut superset).unwrap();
+            assert_eq!(superset, naive_zeta(&data, true));
+        }
+    }
+
+    #[test]
+    fn test_mobius_inverts_zeta() {
+        let data = sample(64);
+
+        let mut subset = data.clone();
+        subset_zeta(&mut subset).unwrap();
+        subset_mobius(&mut subset).unwrap();
+        assert_eq!(subset, data);
+
+        let mut superset = data.clone();
+        superset_zeta(&mut superset).unwrap();
+        superset_mobius(&mut superset).unwrap();
+        assert_eq!(superset, data);
+    }
+
+    #[test]
+    fn test_convolutions_match_naive() {
+        let a = sample(16);
+        let b: Vec<i64> = sample(11).iter().map(|x| x * 3 + 1).collect();
+        assert_eq!(
+            or_convolve(&a, &b).unwrap(),
+            naive_convolve(&a, &b, |i, j| i | j)
+        );
+        assert_eq!(
+            and_convolve(&a, &b).unwrap(),
+            naive_convolve(&a, &b, |i, j| i & j)
+        );
+        assert_eq!(or_convolve::<i64>(&[], &[]).unwrap(), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn test_modular_and_float() {
+        let a: Vec<Mint> = (0..8u64).map(|i| Mint::new(998_244_352 - i)).collect();
+        let mut data = a.clone();
+        subset_zeta(&mut data).unwrap();
+        assert_eq!(data[7], a.iter().fold(Mint::new(0), |sum, &x| sum + x));
+        subset_mobius(&mut data).unwrap();
+        assert_eq!(data, a);
+
+        let c = or_convolve(&[0.5, 0.25], &[2.0, 4.0]).unwrap();
+        assert_eq!(c, vec![1.0, 2.0 + 0.5 + 1.0]);
+    }
+
+    fn naive_subset_convolve(a: &[i64], b: &[i64]) -> Vec<i64> {
+        let n = padded_len(a.len().max(b.len())).unwrap();
+        let (a, b) = (padded(a, n), padded(b, n));
+        let mut c = vec![0; n];
+        for i in 0..n {
+            for j in 0..n {
+                if i & j == 0 {
+                    c[i | j] += a[i] * b[j];
+                }
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn test_subset_convolve_matches_naive() {
+        for log2 in 0..7 {
+            let a = sample(1 << log2);
+            let b: Vec<i64> = a.iter().rev().map(|x| x * 2 - 1).collect();
+            assert_eq!(
+                subset_convolve(&a, &b).unwrap(),
+                naive_subset_convolve(&a, &b),
+                "length {}",
+                1 << log2
+            );
+        }
+
+        let a = sample(5);
+        let b = sample(16);
+        assert_eq!(
+            subset_convolve(&a, &b).unwrap(),
+            naive_subset_convolve(&a, &b)
+        );
+        assert_eq!(subset_convolve::<i64>(&[], &[]).unwrap(), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn test_subset_convolve_modular_and_float() {
+        // Counting ordered partitions into k blocks: k!·S(m, k) for the full set
+        let ones = vec![Mint::new(1); 16];
+        let mut nonempty = ones.clone();
+        nonempty[0] = Mint::new(0);
+        let two_blocks = subset_convolve(&nonempty, &nonempty).unwrap();
+        let three_blocks = subset_convolve(&two_blocks, &nonempty).unwrap();
+        assert_eq!(two_blocks[15].value(), 14); // 2!·S(4, 2) = 2·7
+        assert_eq!(three_blocks[15].value(), 36); // 3!·S(4, 3) = 6·6
+
+        let c = subset_convolve(&[0.5, 2.0], &[4.0, 0.25]).unwrap();
+        assert_eq!(c, vec![2.0, 0.5 * 0.25 + 2.0 * 4.0]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut data = [1, 2, 3];
+        assert_eq!(
+            subset_zeta(&mut data),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+        assert_eq!(
+            superset_mobius(&mut data),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+        assert_eq!(data, [1, 2, 3]);
+    }
+}
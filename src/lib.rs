@@ -2,14 +2,14 @@
 //!
 //! This library provides efficient implementations of the Fast Walsh-Hadamard Transform
 //! for various container types including `Vec<T>`, static arrays `[T; N]`, and
-//! optionally `ndarray::Array1<T>`.
+//! optionally one-dimensional `ndarray` arrays and views of any stride.
 //!
 //! # Features
 //!
 //! - **Fast**: O(n log n) implementation using butterfly operations
 //! - **Generic**: Works with any numeric type implementing `Add + Sub + Copy`
 //! - **Flexible**: Uniform API across different container types via traits
-//! - **Optional dependencies**: ndarray support is feature-gated
+//! - **Optional dependencies**: ndarray, rayon and num-complex support are feature-gated
 //!
 //! # Quick Start
 //!
@@ -42,6 +42,10 @@
 //!
 //! - [`FWHT::fwht_mut`]: In-place transformation (memory efficient)
 //! - [`FWHT::fwht`]: Returns a new container with the result
+//! - [`FWHT::fwht_into`]: Writes the result into a caller-provided buffer
+//! - [`FWHT::fwht_mut_normalized`] / [`FWHT::fwht_normalized`]: Scaled transforms
+//! - [`FWHT::ifwht_mut`] / [`FWHT::ifwht`]: Inverse transforms
+//! - [`FWHT::fwht_mut_ordered`] / [`FWHT::fwht_ordered`]: Sequency or dyadic output
 //!
 //! ## Function-based API
 //!
@@ -49,6 +53,10 @@
 //!
 //! - [`fwht_mut`]: In-place transformation for any `AsMut<[T]>`
 //! - [`fwht`]: Copy-based transformation for any `Clone + AsMut<[T]>`
+//! - [`fwht_into`]: Out-of-place transformation into a preallocated slice
+//! - [`fwht_mut_normalized`], [`fwht_normalized`], [`ifwht_mut`], [`ifwht`]:
+//!   the same with a [`Normalization`] mode
+//! - [`fwht_mut_ordered`], [`fwht_ordered`]: the same with an [`Ordering`]
 //!
 //! # Requirements
 //!
@@ -67,33 +75,33 @@
 //! assert_eq!(result, vec![4.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0]);
 //! ```
 //!
-//! ## Involution Property
+//! ## Normalization and Inverse
 //!
-//! FWHT is its own inverse (up to scaling):
+//! FWHT is its own inverse up to a factor of `n`. The [`Normalization`] mode
+//! chooses where that factor goes, and [`FWHT::ifwht`] undoes the matching
+//! forward transform:
 //!
 //! ```
-//! use fwht::FWHT;
+//! use fwht::{FWHT, Normalization};
 //!
-//! let original = vec![1.0, 2.0, 3.0, 4.0];
-//! let mut data = original.clone();
+//! let original = vec![1.0f64, 2.0, 3.0, 4.0];
 //!
-//! // Apply FWHT twice
-//! data.fwht_mut().unwrap();
-//! data.fwht_mut().unwrap();
+//! for norm in [Normalization::None, Normalization::Orthonormal, Normalization::ByN] {
+//!     let spectrum = original.fwht_normalized(norm).unwrap();
+//!     let restored = spectrum.ifwht(norm).unwrap();
 //!
-//! // Scale by 1/n to recover original
-//! let n = data.len() as f64;
-//! for x in &mut data { *x /= n; }
-//!
-//! // Should equal original (within floating point precision)
-//! for (a, b) in data.iter().zip(original.iter()) {
-//!     assert!((a - b).abs() < 1e-10);
+//!     for (a, b) in restored.iter().zip(original.iter()) {
+//!         assert!((a - b).abs() < 1e-10);
+//!     }
 //! }
 //! ```
 
 // Core algorithm
 pub mod core;
 
+// Error type
+pub mod error;
+
 // Trait definitions
 pub mod traits;
 
@@ -102,10 +110,83 @@ pub mod impls;
 
 pub mod functions;
 
-pub use functions::{fwht, fwht_mut};
+// Normalization modes
+pub mod normalize;
+
+// Output orderings and spectrum permutations
+pub mod ordering;
+
+// SIMD kernels with runtime dispatch
+pub mod simd;
+
+// Batched transforms
+pub mod batch;
+
+// Reusable transform plans
+pub mod plan;
+
+// Autotuning planner and wisdom files
+pub mod planner;
+
+// Boolean functions, ANF and spectra
+pub mod boolean;
+
+// XOR convolution
+pub mod convolution;
+
+// Zeta and Möbius transforms, OR and AND convolution
+pub mod lattice;
+
+// Transforms modulo an integer
+pub mod modular;
+
+// Multithreaded kernels
+#[cfg(feature = "rayon")]
+pub mod parallel;
+
+// Complex-valued transforms
+#[cfg(feature = "num-complex")]
+pub mod complex;
+
+pub use batch::{fwht_batch, fwht_batch_vecs};
+pub use convolution::{
+    xor_autocorrelation, xor_convolution_power, xor_convolve, xor_convolve_mut,
+    xor_cross_correlation,
+};
+pub use error::FwhtError;
+pub use functions::{
+    fwht, fwht_into, fwht_mut, fwht_mut_normalized, fwht_mut_ordered, fwht_normalized,
+    fwht_ordered, ifwht, ifwht_mut,
+};
+pub use lattice::{
+    and_convolve, or_convolve, subset_convolve, subset_mobius, subset_zeta, superset_mobius,
+    superset_zeta,
+};
+pub use modular::{fwht_mod_mut, ifwht_mod_mut, ModInt};
+pub use normalize::{Normalization, Normalize};
+pub use ordering::Ordering;
+pub use plan::{FwhtPlan, PlanOptions};
+pub use planner::{Planner, PlannerMode};
+pub use simd::{fwht_simd, Backend};
 pub use traits::FWHT;
 
-pub use core::{fwht_slice, is_valid_fwht_length, next_power_of_two};
+#[cfg(feature = "rayon")]
+pub use batch::{fwht_batch_par, fwht_batch_vecs_par};
+#[cfg(feature = "rayon")]
+pub use functions::{fwht_par, fwht_par_mut};
+#[cfg(feature = "rayon")]
+pub use parallel::fwht_slice_par;
+
+#[cfg(feature = "ndarray")]
+pub use impls::ndarray::fwht_view_into;
+#[cfg(feature = "ndarray")]
+pub use traits::FWHTAxis;
+
+pub use core::{
+    fwht_interleaved, fwht_slice, fwht_slice_into, fwht_slice_normalized, fwht_slice_ordered,
+    fwht_slice_sequency, fwht_slice_with_kernel, fwht_strided, ifwht_slice, is_valid_fwht_length,
+    next_power_of_two, Kernel,
+};
 
 #[cfg(test)]
 mod integration_tests {
@@ -175,6 +256,39 @@ mod integration_tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_inverse_consistency_across_containers() {
+        let input = [2.0, -1.0, 0.5, 3.0];
+
+        let vec_result = input.to_vec().fwht_normalized(Normalization::ByN).unwrap();
+        let array_result = input.fwht_normalized(Normalization::ByN).unwrap();
+        let function_result = fwht_normalized(&input, Normalization::ByN).unwrap();
+        assert_eq!(vec_result, array_result.to_vec());
+        assert_eq!(array_result, function_result);
+
+        assert_eq!(array_result.ifwht(Normalization::ByN).unwrap(), input);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_consistency_across_containers() {
+        let input = [2.0, -1.0, 0.5, 3.0];
+        let expected = input.fwht().unwrap();
+
+        assert_eq!(input.fwht_par().unwrap(), expected);
+        assert_eq!(input.to_vec().fwht_par().unwrap(), expected.to_vec());
+
+        #[cfg(feature = "ndarray")]
+        {
+            use ::ndarray::Array1;
+            let ndarray_data = Array1::from(input.to_vec());
+            assert_eq!(
+                ndarray_data.fwht_par().unwrap(),
+                Array1::from(expected.to_vec())
+            );
+        }
+    }
+
     #[test]
     fn test_edge_cases() {
         let empty_vec: Vec<f64> = vec![];
@@ -0,0 +1,605 @@
+//! Transforms over the integers modulo `m`
+//!
+//! Modulo a prime `p`, the transform has an exact inverse: apply it again and
+//! multiply by `n⁻¹ mod p`. This makes exact XOR convolutions possible
+//! without floating point error or integer overflow.
+//!
+//! Two interfaces are provided:
+//!
+//! - [`ModInt<P>`]: an element type with a compile-time odd modulus, stored in
+//!   Montgomery form. It works with every generic entry point of the crate
+//!   (kernels, plans, batches) and implements [`Normalize`], so
+//!   [`Normalization::ByN`] multiplies by `n⁻¹ mod P`.
+//! - [`fwht_mod_mut`] and [`ifwht_mod_mut`]: transforms of plain `u32` or
+//!   `u64` residues with a modulus chosen at runtime.
+//!
+//! Every operation keeps its operands below the modulus and handles moduli up
+//! to the full width of the word, so no intermediate value ever overflows.
+//!
+//! # Examples
+//!
+//! ```
+//! use fwht::modular::ModInt;
+//! use fwht::{FWHT, Normalization};
+//!
+//! type Mint = ModInt<998_244_353>;
+//!
+//! let data: Vec<Mint> = [1, 2, 3, 4].into_iter().map(Mint::new).collect();
+//! let spectrum = data.fwht().unwrap();
+//! assert_eq!(spectrum[0].value(), 10);
+//! assert_eq!(spectrum[1].value(), 998_244_351); // -2
+//!
+//! let restored = spectrum.ifwht(Normalization::None).unwrap();
+//! assert_eq!(restored, data);
+//! ```
+//!
+//! [`Normalization`]: crate::Normalization
+//! [`Normalization::ByN`]: crate::Normalization::ByN
+
+use crate::core::validate_length;
+use crate::error::FwhtError;
+use crate::normalize::Normalize;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Montgomery multiplication modulo an odd `u64` modulus
+///
+/// Values in Montgomery form are `x·R mod m` with `R = 2⁶⁴`. The reduction
+/// subtracts the high halves instead of adding them, so it never overflows
+/// even when `m` is close to `2⁶⁴`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Montgomery {
+    modulus: u64,
+    /// `m⁻¹ mod R`
+    inverse: u64,
+    /// `R² mod m`
+    r2: u64,
+}
+
+impl Montgomery {
+    /// Precomputes the constants for an odd `modulus`
+    const fn new(modulus: u64) -> Self {
+        // Newton's iteration doubles the correct low bits each step
+        let mut inverse = modulus;
+        let mut i = 0;
+        while i < 5 {
+            inverse = inverse.wrapping_mul(2u64.wrapping_sub(modulus.wrapping_mul(inverse)));
+            i += 1;
+        }
+
+        let r = ((1u128 << 64) % modulus as u128) as u64;
+        let r2 = ((r as u128 * r as u128) % modulus as u128) as u64;
+        Montgomery {
+            modulus,
+            inverse,
+            r2,
+        }
+    }
+
+    /// Returns `t·R⁻¹ mod m` for `t < m·R`
+    #[inline]
+    const fn reduce(&self, t: u128) -> u64 {
+        let q = (t as u64).wrapping_mul(self.inverse);
+        let qm = ((q as u128 * self.modulus as u128) >> 64) as u64;
+        let high = (t >> 64) as u64;
+        if high >= qm {
+            high - qm
+        } else {
+            high.wrapping_sub(qm).wrapping_add(self.modulus)
+        }
+    }
+
+    /// Returns `a·b·R⁻¹ mod m`
+    #[inline]
+    const fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    /// Converts a value to Montgomery form
+    #[inline]
+    const fn encode(&self, value: u64) -> u64 {
+        self.mul(value % self.modulus, self.r2)
+    }
+
+    /// Converts a value out of Montgomery form
+    #[inline]
+    const fn decode(&self, value: u64) -> u64 {
+        self.reduce(value as u128)
+    }
+}
+
+/// Returns `a⁻¹ mod m` for `a < m`, if `a` is coprime with `m`
+fn inverse_mod(a: u64, m: u64) -> Option<u64> {
+    // Extended Euclid, tracking only the coefficient of `a`
+    let (mut r0, mut r1) = (m as i128, a as i128);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+    (r0 == 1).then(|| t0.rem_euclid(m as i128) as u64)
+}
+
+/// Returns `(a + b) mod m` for `a, b < m`
+#[inline]
+fn add_mod(a: u64, b: u64, m: u64) -> u64 {
+    let (sum, carry) = a.overflowing_add(b);
+    if carry || sum >= m {
+        sum.wrapping_sub(m)
+    } else {
+        sum
+    }
+}
+
+/// Returns `(a - b) mod m` for `a, b < m`
+#[inline]
+fn sub_mod(a: u64, b: u64, m: u64) -> u64 {
+    let (difference, borrow) = a.overflowing_sub(b);
+    if borrow {
+        difference.wrapping_add(m)
+    } else {
+        difference
+    }
+}
+
+/// An integer modulo the odd constant `P`
+///
+/// Values are stored in Montgomery form, which makes multiplication (and
+/// therefore normalization) free of divisions. Addition and subtraction, the
+/// only operations of the butterflies, are the same in either form.
+///
+/// `P` must be odd and greater than 1; this is checked at compile time when
+/// a value is created. Any such `P` up to `u64::MAX` is supported. Inverses,
+/// including the `n⁻¹` of [`Normalization::ByN`], exist when `P` is prime or
+/// more generally coprime with the operand.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::modular::ModInt;
+///
+/// type Mint = ModInt<7>;
+///
+/// let a = Mint::new(5);
+/// let b = Mint::new(4);
+/// assert_eq!((a + b).value(), 2);
+/// assert_eq!((a - b).value(), 1);
+/// assert_eq!((a * b).value(), 6);
+/// assert_eq!(a.inv().unwrap().value(), 3);
+/// ```
+///
+/// [`Normalization::ByN`]: crate::Normalization::ByN
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ModInt<const P: u64> {
+    /// The value in Montgomery form
+    form: u64,
+}
+
+impl<const P: u64> ModInt<P> {
+    /// The modulus
+    pub const MODULUS: u64 = P;
+
+    const MONTGOMERY: Montgomery = {
+        assert!(
+            P % 2 == 1 && P > 1,
+            "ModInt modulus must be odd and greater than 1"
+        );
+        Montgomery::new(P)
+    };
+
+    /// Returns `value mod P`
+    pub const fn new(value: u64) -> Self {
+        ModInt {
+            form: Self::MONTGOMERY.encode(value),
+        }
+    }
+
+    /// Returns the residue in `0..P`
+    pub const fn value(self) -> u64 {
+        Self::MONTGOMERY.decode(self.form)
+    }
+
+    /// Returns `self` raised to the power `exponent`
+    pub fn pow(self, mut exponent: u64) -> Self {
+        let mut base = self;
+        let mut result = ModInt::new(1);
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// Returns the multiplicative inverse, if `self` is coprime with `P`
+    pub fn inv(self) -> Option<Self> {
+        inverse_mod(self.value(), P).map(ModInt::new)
+    }
+}
+
+impl<const P: u64> From<u64> for ModInt<P> {
+    fn from(value: u64) -> Self {
+        ModInt::new(value)
+    }
+}
+
+impl<const P: u64> From<u32> for ModInt<P> {
+    fn from(value: u32) -> Self {
+        ModInt::new(value as u64)
+    }
+}
+
+impl<const P: u64> Add for ModInt<P> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        ModInt {
+            form: add_mod(self.form, rhs.form, P),
+        }
+    }
+}
+
+impl<const P: u64> Sub for ModInt<P> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        ModInt {
+            form: sub_mod(self.form, rhs.form, P),
+        }
+    }
+}
+
+impl<const P: u64> Mul for ModInt<P> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        ModInt {
+            form: Self::MONTGOMERY.mul(self.form, rhs.form),
+        }
+    }
+}
+
+impl<const P: u64> Neg for ModInt<P> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        ModInt {
+            form: sub_mod(0, self.form, P),
+        }
+    }
+}
+
+impl<const P: u64> fmt::Debug for ModInt<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value(), P)
+    }
+}
+
+impl<const P: u64> fmt::Display for ModInt<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value(), f)
+    }
+}
+
+impl<const P: u64> Normalize for ModInt<P> {
+    type Factor = ModInt<P>;
+
+    fn inv_len(n: usize) -> Option<Self> {
+        ModInt::new(n as u64).inv()
+    }
+
+    fn inv_sqrt_len(n: usize) -> Option<Self> {
+        let log2 = n.trailing_zeros();
+        if log2.is_multiple_of(2) {
+            Self::inv_len(1 << (log2 / 2))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn scale(self, factor: Self) -> Self {
+        self * factor
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Unsigned words usable as residues by [`fwht_mod_mut`]
+///
+/// Implemented for `u32` and `u64`. This trait is sealed.
+pub trait ModWord: Copy + sealed::Sealed {
+    #[doc(hidden)]
+    fn to_u64(self) -> u64;
+
+    #[doc(hidden)]
+    fn from_u64(value: u64) -> Self;
+}
+
+macro_rules! impl_mod_word {
+    ($($t:ty),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl ModWord for $t {
+                #[inline]
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+
+                #[inline]
+                fn from_u64(value: u64) -> $t {
+                    value as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_mod_word!(u32, u64);
+
+/// FWHT modulo `modulus` on a slice of residues
+///
+/// Values are first reduced modulo `modulus`, so any input is accepted. The
+/// modulus can be any nonzero value up to `W::MAX`.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of
+/// 2, and [`FwhtError::InvalidModulus`] if `modulus` is 0.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::modular::fwht_mod_mut;
+///
+/// let mut data = [1u32, 2, 3, 4];
+/// fwht_mod_mut(&mut data, 7).unwrap();
+/// assert_eq!(data, [3, 5, 3, 0]); // [10, -2, -4, 0] mod 7
+/// ```
+pub fn fwht_mod_mut<W: ModWord>(data: &mut [W], modulus: W) -> Result<(), FwhtError> {
+    let m = validate_mod(data.len(), modulus)?;
+    reduce(data, m);
+    mod_stages(data, m, data.len());
+    Ok(())
+}
+
+/// Inverse FWHT modulo `modulus` on a slice of residues
+///
+/// Exactly inverts [`fwht_mod_mut`]: the transform is applied again and
+/// multiplied by `n⁻¹ mod modulus`, using Montgomery multiplication.
+///
+/// # Errors
+///
+/// Returns the errors of [`fwht_mod_mut`], and
+/// [`FwhtError::UnsupportedNormalization`] if `n` has no inverse modulo
+/// `modulus` (that is, the modulus is even and `n > 1`).
+///
+/// # Examples
+///
+/// ```
+/// use fwht::modular::{fwht_mod_mut, ifwht_mod_mut};
+///
+/// let p = (1u64 << 61) - 1;
+/// let mut data = [p - 1, 2, 3, 4];
+/// fwht_mod_mut(&mut data, p).unwrap();
+/// ifwht_mod_mut(&mut data, p).unwrap();
+/// assert_eq!(data, [p - 1, 2, 3, 4]);
+/// ```
+pub fn ifwht_mod_mut<W: ModWord>(data: &mut [W], modulus: W) -> Result<(), FwhtError> {
+    let m = validate_mod(data.len(), modulus)?;
+    let n = data.len();
+    if n <= 1 || m == 1 {
+        reduce(data, m);
+        return Ok(());
+    }
+    if m % 2 == 0 {
+        return Err(FwhtError::UnsupportedNormalization { len: n });
+    }
+
+    // Multiplying by a factor in Montgomery form yields a plain result
+    let montgomery = Montgomery::new(m);
+    let inverse =
+        inverse_mod(n as u64 % m, m).ok_or(FwhtError::UnsupportedNormalization { len: n })?;
+    let factor = montgomery.encode(inverse);
+
+    reduce(data, m);
+    mod_stages(data, m, n / 2);
+    let (lo, hi) = data.split_at_mut(n / 2);
+    for (a, b) in lo.iter_mut().zip(hi.iter_mut()) {
+        let x = a.to_u64();
+        let y = b.to_u64();
+        *a = W::from_u64(montgomery.mul(add_mod(x, y, m), factor));
+        *b = W::from_u64(montgomery.mul(sub_mod(x, y, m), factor));
+    }
+    Ok(())
+}
+
+/// Checks the length and modulus of a modular transform
+fn validate_mod<W: ModWord>(len: usize, modulus: W) -> Result<u64, FwhtError> {
+    let m = modulus.to_u64();
+    if m == 0 {
+        return Err(FwhtError::InvalidModulus { modulus: m });
+    }
+    validate_length(len)?;
+    Ok(m)
+}
+
+/// Reduces every element of `data` modulo `m`
+fn reduce<W: ModWord>(data: &mut [W], m: u64) {
+    for x in data.iter_mut() {
+        if x.to_u64() >= m {
+            *x = W::from_u64(x.to_u64() % m);
+        }
+    }
+}
+
+/// Runs the butterfly stages with half-width below `until` modulo `m`
+fn mod_stages<W: ModWord>(data: &mut [W], m: u64, until: usize) {
+    let mut h = 1;
+    while h < until {
+        for block in data.chunks_exact_mut(h * 2) {
+            let (lo, hi) = block.split_at_mut(h);
+            for (a, b) in lo.iter_mut().zip(hi.iter_mut()) {
+                let x = a.to_u64();
+                let y = b.to_u64();
+                *a = W::from_u64(add_mod(x, y, m));
+                *b = W::from_u64(sub_mod(x, y, m));
+            }
+        }
+        h *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{fwht_slice, fwht_slice_normalized, ifwht_slice};
+    use crate::normalize::Normalization;
+
+    const LARGE: u64 = u64::MAX - 58; // 2⁶⁴ - 59, the largest 64-bit prime
+    const LARGE_U32: u32 = u32::MAX - 4; // 2³² - 5, the largest 32-bit prime
+
+    fn naive_xor_convolution(a: &[u64], b: &[u64], m: u64) -> Vec<u64> {
+        let mut result = vec![0u64; a.len()];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                let product = (x as u128 * y as u128 % m as u128) as u64;
+                result[i ^ j] = add_mod(result[i ^ j], product, m);
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_modint_arithmetic_near_word_size() {
+        type Mint = ModInt<LARGE>;
+
+        let a = Mint::new(LARGE - 1);
+        let b = Mint::new(LARGE - 2);
+        assert_eq!((a + b).value(), LARGE - 3);
+        assert_eq!((b - a).value(), LARGE - 1);
+        assert_eq!((a * b).value(), 2);
+        assert_eq!((-a).value(), 1);
+        assert_eq!(Mint::new(u64::MAX).value(), 58);
+        assert_eq!((a * a.inv().unwrap()).value(), 1);
+        assert_eq!(Mint::new(3).pow(LARGE - 1).value(), 1);
+        assert_eq!(Mint::new(0).inv(), None);
+    }
+
+    #[test]
+    fn test_modint_composite_modulus() {
+        type Mint = ModInt<15>;
+        assert_eq!(Mint::new(6).inv(), None);
+        assert_eq!(Mint::new(7).inv().unwrap().value(), 13);
+        assert_eq!(format!("{:?}", Mint::new(17)), "2 (mod 15)");
+        assert_eq!(Mint::new(17).to_string(), "2");
+    }
+
+    #[test]
+    fn test_modint_transform_round_trip() {
+        type Mint = ModInt<LARGE>;
+
+        let data: Vec<Mint> = (0..64u64).map(|i| Mint::new(LARGE - 1 - i * i)).collect();
+        for norm in [
+            Normalization::None,
+            Normalization::Orthonormal,
+            Normalization::ByN,
+        ] {
+            let mut spectrum = data.clone();
+            fwht_slice_normalized(&mut spectrum, norm).unwrap();
+            ifwht_slice(&mut spectrum, norm).unwrap();
+            assert_eq!(spectrum, data, "{:?}", norm);
+        }
+
+        let mut odd = data[..8].to_vec();
+        assert_eq!(
+            fwht_slice_normalized(&mut odd, Normalization::Orthonormal),
+            Err(FwhtError::UnsupportedNormalization { len: 8 })
+        );
+    }
+
+    #[test]
+    fn test_runtime_matches_modint() {
+        type Mint = ModInt<LARGE>;
+
+        let values: Vec<u64> = (0..256u64)
+            .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+            .collect();
+        let mut expected: Vec<Mint> = values.iter().map(|&v| Mint::new(v)).collect();
+        fwht_slice(&mut expected).unwrap();
+
+        let mut data = values.clone();
+        fwht_mod_mut(&mut data, LARGE).unwrap();
+        let expected: Vec<u64> = expected.iter().map(|x| x.value()).collect();
+        assert_eq!(data, expected);
+
+        ifwht_mod_mut(&mut data, LARGE).unwrap();
+        let reduced: Vec<u64> = values.iter().map(|v| v % LARGE).collect();
+        assert_eq!(data, reduced);
+    }
+
+    #[test]
+    fn test_runtime_u32_round_trip() {
+        let values: Vec<u32> = (0..128u32).map(|i| u32::MAX - i * 7).collect();
+        let mut data = values.clone();
+        fwht_mod_mut(&mut data, LARGE_U32).unwrap();
+        assert!(data.iter().all(|&x| x < LARGE_U32));
+        ifwht_mod_mut(&mut data, LARGE_U32).unwrap();
+        let reduced: Vec<u32> = values.iter().map(|v| v % LARGE_U32).collect();
+        assert_eq!(data, reduced);
+    }
+
+    #[test]
+    fn test_exact_xor_convolution() {
+        let m = 998_244_353;
+        let a: Vec<u64> = (0..32).map(|i| (i * i * 7919) % m).collect();
+        let b: Vec<u64> = (0..32).map(|i| (m - 1 - i * 104_729) % m).collect();
+
+        let mut fa = a.clone();
+        let mut fb = b.clone();
+        fwht_mod_mut(&mut fa, m).unwrap();
+        fwht_mod_mut(&mut fb, m).unwrap();
+        let mut product: Vec<u64> = fa.iter().zip(&fb).map(|(&x, &y)| x * y % m).collect();
+        ifwht_mod_mut(&mut product, m).unwrap();
+
+        assert_eq!(product, naive_xor_convolution(&a, &b, m));
+    }
+
+    #[test]
+    fn test_runtime_errors() {
+        let mut data = [1u64, 2, 3, 4];
+        assert_eq!(
+            fwht_mod_mut(&mut data, 0),
+            Err(FwhtError::InvalidModulus { modulus: 0 })
+        );
+        assert_eq!(
+            ifwht_mod_mut(&mut data, 16),
+            Err(FwhtError::UnsupportedNormalization { len: 4 })
+        );
+        assert_eq!(data, [1, 2, 3, 4]);
+
+        let mut odd = [1u32, 2, 3];
+        assert_eq!(
+            fwht_mod_mut(&mut odd, 7),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+
+        // Even moduli still support the forward transform
+        fwht_mod_mut(&mut data, 16).unwrap();
+        assert_eq!(data, [10, 14, 12, 0]);
+
+        let mut single = [9u32];
+        ifwht_mod_mut(&mut single, 4).unwrap();
+        assert_eq!(single, [1]);
+    }
+}
@@ -0,0 +1,274 @@
+//! Normalization modes for the FWHT
+//!
+//! The raw butterfly network computes `H·x` where `H` has ±1 entries. Since
+//! `H·H = n·I`, the transform is its own inverse up to a factor of `n`. This
+//! module defines how that factor is distributed between the forward and the
+//! inverse transforms, and which element types can be scaled.
+
+use crate::error::FwhtError;
+#[cfg(feature = "num-complex")]
+use num_complex::Complex;
+
+/// How the forward transform is scaled
+///
+/// Each mode has a matching inverse (see [`Normalization::inverse`]) such
+/// that applying the forward transform followed by the inverse transform with
+/// the same mode recovers the input.
+///
+/// For signed integers the division by `n` or `√n` truncates toward zero.
+/// Only [`Normalization::None`] followed by the inverse with the same mode
+/// round-trips exactly for every integer input; `Orthonormal` and `ByN`
+/// forward transforms lose the remainders of coefficients that are not
+/// multiples of the factor.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::{FWHT, Normalization};
+///
+/// let original = vec![1.0, 2.0, 3.0, 4.0];
+/// let spectrum = original.fwht_normalized(Normalization::ByN).unwrap();
+/// assert_eq!(spectrum, vec![2.5, -0.5, -1.0, 0.0]);
+///
+/// let restored = spectrum.ifwht(Normalization::ByN).unwrap();
+/// assert_eq!(restored, original);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Normalization {
+    /// No scaling: the raw ±1 butterfly sum (the inverse divides by `n`)
+    #[default]
+    None,
+    /// Scale by `1/√n`, making the transform orthonormal and self-inverse
+    Orthonormal,
+    /// Scale by `1/n` (the inverse applies no scaling)
+    ByN,
+}
+
+impl Normalization {
+    /// Returns the mode to apply in the forward direction to invert `self`
+    ///
+    /// Because `H·H = n·I`, inverting a transform normalized with `self` is
+    /// the same as transforming again with the complementary scaling.
+    ///
+    /// ```
+    /// use fwht::Normalization;
+    ///
+    /// assert_eq!(Normalization::None.inverse(), Normalization::ByN);
+    /// assert_eq!(Normalization::ByN.inverse(), Normalization::None);
+    /// assert_eq!(Normalization::Orthonormal.inverse(), Normalization::Orthonormal);
+    /// ```
+    pub const fn inverse(self) -> Self {
+        match self {
+            Normalization::None => Normalization::ByN,
+            Normalization::Orthonormal => Normalization::Orthonormal,
+            Normalization::ByN => Normalization::None,
+        }
+    }
+
+    /// Returns the scale factor for a transform of length `n`
+    ///
+    /// Returns `Ok(None)` when no scaling is needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::UnsupportedNormalization`] if the factor cannot be
+    /// represented in `T` (for example `1/√2` for integers).
+    pub(crate) fn factor<T: Normalize>(self, n: usize) -> Result<Option<T::Factor>, FwhtError> {
+        let factor = match self {
+            _ if n <= 1 => return Ok(None),
+            Normalization::None => return Ok(None),
+            Normalization::Orthonormal => T::inv_sqrt_len(n),
+            Normalization::ByN => T::inv_len(n),
+        };
+
+        factor
+            .map(Some)
+            .ok_or(FwhtError::UnsupportedNormalization { len: n })
+    }
+}
+
+/// Element types that can be scaled by a [`Normalization`]
+///
+/// The scale factor is computed once per transform. The generic and sequency
+/// kernels apply it during the last butterfly stage, so normalization costs
+/// no extra pass over the data; SIMD and multithreaded
+/// [plans](crate::plan::FwhtPlan) scale the result in a separate pass.
+///
+/// Floating point types multiply by `1/n` or `1/√n`. Signed integers divide
+/// by `n` or `√n`, which is exact when inverting an unnormalized transform of
+/// integer data; `√n` is only available when `n` is a power of 4. Anywhere
+/// else the integer division truncates toward zero, so a normalized forward
+/// transform of integers is generally not invertible.
+///
+/// With the "num-complex" feature, `Complex<T>` scales both parts by the
+/// factor of `T`.
+pub trait Normalize: Copy {
+    /// Precomputed per-length scale factor
+    type Factor: Copy;
+
+    /// Returns the factor that divides by `n`, if representable
+    fn inv_len(n: usize) -> Option<Self::Factor>;
+
+    /// Returns the factor that divides by `√n`, if representable
+    fn inv_sqrt_len(n: usize) -> Option<Self::Factor>;
+
+    /// Applies a factor to a value
+    fn scale(self, factor: Self::Factor) -> Self;
+}
+
+macro_rules! impl_normalize_float {
+    ($($t:ty),*) => {
+        $(
+            impl Normalize for $t {
+                type Factor = $t;
+
+                fn inv_len(n: usize) -> Option<$t> {
+                    Some(1.0 / n as $t)
+                }
+
+                fn inv_sqrt_len(n: usize) -> Option<$t> {
+                    Some(1.0 / (n as $t).sqrt())
+                }
+
+                #[inline]
+                fn scale(self, factor: $t) -> $t {
+                    self * factor
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_normalize_int {
+    ($($t:ty),*) => {
+        $(
+            // Scaling divides, truncating toward zero
+            impl Normalize for $t {
+                type Factor = $t;
+
+                fn inv_len(n: usize) -> Option<$t> {
+                    <$t>::try_from(n).ok()
+                }
+
+                fn inv_sqrt_len(n: usize) -> Option<$t> {
+                    let log2 = n.trailing_zeros();
+                    if log2 % 2 == 0 {
+                        <$t>::try_from(1usize << (log2 / 2)).ok()
+                    } else {
+                        None
+                    }
+                }
+
+                #[inline]
+                fn scale(self, factor: $t) -> $t {
+                    self / factor
+                }
+            }
+        )*
+    };
+}
+
+impl_normalize_float!(f32, f64);
+impl_normalize_int!(i8, i16, i32, i64, i128, isize);
+
+#[cfg(feature = "num-complex")]
+impl<T: Normalize> Normalize for Complex<T> {
+    type Factor = T::Factor;
+
+    fn inv_len(n: usize) -> Option<T::Factor> {
+        T::inv_len(n)
+    }
+
+    fn inv_sqrt_len(n: usize) -> Option<T::Factor> {
+        T::inv_sqrt_len(n)
+    }
+
+    #[inline]
+    fn scale(self, factor: T::Factor) -> Self {
+        Complex::new(self.re.scale(factor), self.im.scale(factor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inverse_is_involution() {
+        for norm in [
+            Normalization::None,
+            Normalization::Orthonormal,
+            Normalization::ByN,
+        ] {
+            assert_eq!(norm.inverse().inverse(), norm);
+        }
+    }
+
+    #[test]
+    fn test_default_is_none() {
+        assert_eq!(Normalization::default(), Normalization::None);
+    }
+
+    #[test]
+    fn test_float_factors() {
+        assert_eq!(Normalization::ByN.factor::<f64>(8), Ok(Some(0.125)));
+        assert_eq!(Normalization::Orthonormal.factor::<f64>(16), Ok(Some(0.25)));
+        assert_eq!(Normalization::None.factor::<f64>(16), Ok(None));
+    }
+
+    #[test]
+    fn test_trivial_lengths_need_no_factor() {
+        assert_eq!(Normalization::ByN.factor::<i32>(0), Ok(None));
+        assert_eq!(Normalization::Orthonormal.factor::<i32>(1), Ok(None));
+    }
+
+    #[test]
+    fn test_integer_factors() {
+        assert_eq!(Normalization::ByN.factor::<i32>(8), Ok(Some(8)));
+        assert_eq!(Normalization::Orthonormal.factor::<i32>(16), Ok(Some(4)));
+        assert_eq!(
+            Normalization::Orthonormal.factor::<i32>(8),
+            Err(FwhtError::UnsupportedNormalization { len: 8 })
+        );
+        assert_eq!(
+            Normalization::ByN.factor::<i8>(256),
+            Err(FwhtError::UnsupportedNormalization { len: 256 })
+        );
+    }
+
+    #[test]
+    fn test_integer_scaling_truncates() {
+        let mut data = [1i32, 2, 3, 4];
+        crate::core::fwht_slice_normalized(&mut data, Normalization::ByN).unwrap();
+        assert_eq!(data, [2, 0, -1, 0]);
+
+        crate::core::ifwht_slice(&mut data, Normalization::ByN).unwrap();
+        assert_eq!(data, [1, 1, 3, 3]);
+
+        // The unnormalized forward transform inverts exactly
+        let mut data = [1i32, 2, 3, 4];
+        crate::core::fwht_slice_normalized(&mut data, Normalization::None).unwrap();
+        crate::core::ifwht_slice(&mut data, Normalization::None).unwrap();
+        assert_eq!(data, [1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "num-complex")]
+    #[test]
+    fn test_complex_scales_both_parts() {
+        let factor = Normalization::ByN
+            .factor::<Complex<f64>>(4)
+            .unwrap()
+            .unwrap();
+        assert_eq!(factor, 0.25);
+        assert_eq!(
+            Complex::new(2.0, -8.0).scale(factor),
+            Complex::new(0.5, -2.0)
+        );
+
+        let factor = Normalization::ByN
+            .factor::<Complex<i32>>(4)
+            .unwrap()
+            .unwrap();
+        assert_eq!(Complex::new(8, -4).scale(factor), Complex::new(2, -1));
+    }
+}
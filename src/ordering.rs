@@ -0,0 +1,319 @@
+//! Output orderings of the Walsh-Hadamard spectrum
+//!
+//! The butterfly network produces coefficients in natural (Hadamard) order.
+//! This module provides the [`Ordering`] enum and in-place permutations that
+//! convert spectra between natural, sequency (Walsh) and dyadic (Paley)
+//! orderings.
+//!
+//! With `rev` the bit reversal over `log2 n` bits and `gray(k) = k ^ (k >> 1)`,
+//! the coefficient at index `k` of each ordering is the natural coefficient at:
+//!
+//! | Ordering            | Natural index      |
+//! |---------------------|--------------------|
+//! | [`Ordering::Natural`]  | `k`                |
+//! | [`Ordering::Dyadic`]   | `rev(k)`           |
+//! | [`Ordering::Sequency`] | `rev(gray(k))`     |
+//!
+//! All permutations run in place without allocating.
+
+use crate::core::validate_length;
+use crate::error::FwhtError;
+
+/// Ordering of the transform coefficients
+///
+/// # Examples
+///
+/// ```
+/// use fwht::{FWHT, Ordering};
+///
+/// let data = vec![1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+///
+/// let natural = data.fwht_ordered(Ordering::Natural).unwrap();
+/// assert_eq!(natural, vec![4.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0]);
+///
+/// // A single step has its energy at the lowest sequencies
+/// let sequency = data.fwht_ordered(Ordering::Sequency).unwrap();
+/// assert_eq!(sequency, vec![4.0, 4.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Ordering {
+    /// Natural (Hadamard) ordering, as produced by the butterfly network
+    #[default]
+    Natural,
+    /// Sequency (Walsh) ordering: rows sorted by number of sign changes
+    Sequency,
+    /// Dyadic (Paley) ordering: natural ordering with bit-reversed indices
+    Dyadic,
+}
+
+/// Reverses the lowest `bits` bits of `i`
+#[inline]
+pub(crate) fn reverse_bits(i: usize, bits: u32) -> usize {
+    if bits == 0 {
+        0
+    } else {
+        i.reverse_bits() >> (usize::BITS - bits)
+    }
+}
+
+/// Binary-reflected Gray code of `i`
+#[inline]
+pub(crate) fn gray(i: usize) -> usize {
+    i ^ (i >> 1)
+}
+
+/// Inverse of [`gray`]: prefix XOR of the bits of `g`
+#[inline]
+pub(crate) fn inverse_gray(mut g: usize) -> usize {
+    let mut shift = 1;
+    while shift < usize::BITS {
+        g ^= g >> shift;
+        shift *= 2;
+    }
+    g
+}
+
+/// Permutes `data` in place by bit-reversing indices
+///
+/// After the call, `data[k]` holds the element previously at index `rev(k)`.
+/// This permutation is its own inverse and converts between natural and
+/// dyadic orderings in both directions.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the slice length is not a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::ordering::bit_reverse_permute;
+///
+/// let mut data = [0, 1, 2, 3, 4, 5, 6, 7];
+/// bit_reverse_permute(&mut data).unwrap();
+/// assert_eq!(data, [0, 4, 2, 6, 1, 5, 3, 7]);
+/// ```
+pub fn bit_reverse_permute<T>(data: &mut [T]) -> Result<(), FwhtError> {
+    validate_length(data.len())?;
+    bit_reverse_unchecked(data);
+    Ok(())
+}
+
+/// Bit-reverses a slice whose length is already validated
+pub(crate) fn bit_reverse_unchecked<T>(data: &mut [T]) {
+    let n = data.len();
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = reverse_bits(i, bits);
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+}
+
+/// Permutes `data` in place so that `data[k]` takes the element at `gray(k)`
+///
+/// This converts a dyadic-ordered spectrum into sequency order. Its inverse
+/// is [`inverse_gray_code_permute`].
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the slice length is not a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::ordering::gray_code_permute;
+///
+/// let mut data = [0, 1, 2, 3, 4, 5, 6, 7];
+/// gray_code_permute(&mut data).unwrap();
+/// assert_eq!(data, [0, 1, 3, 2, 6, 7, 5, 4]);
+/// ```
+pub fn gray_code_permute<T>(data: &mut [T]) -> Result<(), FwhtError> {
+    validate_length(data.len())?;
+    permute_cycles(data, gray);
+    Ok(())
+}
+
+/// Permutes `data` in place so that `data[gray(k)]` takes the element at `k`
+///
+/// This converts a sequency-ordered spectrum into dyadic order and undoes
+/// [`gray_code_permute`].
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the slice length is not a power of 2.
+pub fn inverse_gray_code_permute<T>(data: &mut [T]) -> Result<(), FwhtError> {
+    validate_length(data.len())?;
+    permute_cycles(data, inverse_gray);
+    Ok(())
+}
+
+/// Converts a spectrum in place from one ordering to another
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the slice length is not a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::ordering::reorder;
+/// use fwht::{fwht_slice, Ordering};
+///
+/// let mut data = [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+/// fwht_slice(&mut data).unwrap();
+///
+/// reorder(&mut data, Ordering::Natural, Ordering::Sequency).unwrap();
+/// assert_eq!(data, [4.0, 4.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+///
+/// reorder(&mut data, Ordering::Sequency, Ordering::Natural).unwrap();
+/// assert_eq!(data, [4.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0]);
+/// ```
+pub fn reorder<T>(data: &mut [T], from: Ordering, to: Ordering) -> Result<(), FwhtError> {
+    validate_length(data.len())?;
+
+    match (from, to) {
+        (Ordering::Natural, Ordering::Dyadic) | (Ordering::Dyadic, Ordering::Natural) => {
+            bit_reverse_permute(data)
+        }
+        (Ordering::Dyadic, Ordering::Sequency) => gray_code_permute(data),
+        (Ordering::Sequency, Ordering::Dyadic) => inverse_gray_code_permute(data),
+        (Ordering::Natural, Ordering::Sequency) => {
+            bit_reverse_permute(data)?;
+            gray_code_permute(data)
+        }
+        (Ordering::Sequency, Ordering::Natural) => {
+            inverse_gray_code_permute(data)?;
+            bit_reverse_permute(data)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Applies `data[k] <- data[source(k)]` in place by following cycles
+///
+/// Each cycle is rotated once, starting from its smallest index. The Gray
+/// code maps have cycles of length at most `2 log2 n`, so checking for the
+/// cycle leader keeps the total cost at O(n log n) without extra memory.
+fn permute_cycles<T, F>(data: &mut [T], source: F)
+where
+    F: Fn(usize) -> usize,
+{
+    let n = data.len();
+
+    for start in 0..n {
+        let mut k = source(start);
+        while k > start {
+            k = source(k);
+        }
+        if k < start {
+            continue;
+        }
+
+        let mut current = start;
+        let mut next = source(current);
+        while next != start {
+            data.swap(current, next);
+            current = next;
+            next = source(current);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reverse_bits() {
+        assert_eq!(reverse_bits(0b001, 3), 0b100);
+        assert_eq!(reverse_bits(0b110, 3), 0b011);
+        assert_eq!(reverse_bits(0, 0), 0);
+    }
+
+    #[test]
+    fn test_gray_round_trip() {
+        for i in 0..1024 {
+            assert_eq!(inverse_gray(gray(i)), i);
+            assert_eq!(gray(inverse_gray(i)), i);
+        }
+    }
+
+    #[test]
+    fn test_bit_reverse_is_involution() {
+        let original: Vec<usize> = (0..64).collect();
+        let mut data = original.clone();
+        bit_reverse_permute(&mut data).unwrap();
+        assert_ne!(data, original);
+        bit_reverse_permute(&mut data).unwrap();
+        assert_eq!(data, original);
+    }
+
+    #[test]
+    fn test_gray_code_permute_matches_definition() {
+        for log2 in 0..10 {
+            let n = 1 << log2;
+            let mut data: Vec<usize> = (0..n).collect();
+            gray_code_permute(&mut data).unwrap();
+            let expected: Vec<usize> = (0..n).map(gray).collect();
+            assert_eq!(data, expected);
+
+            inverse_gray_code_permute(&mut data).unwrap();
+            assert_eq!(data, (0..n).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_reorder_round_trips() {
+        let orderings = [Ordering::Natural, Ordering::Sequency, Ordering::Dyadic];
+        let original: Vec<usize> = (0..32).collect();
+
+        for &from in &orderings {
+            for &to in &orderings {
+                let mut data = original.clone();
+                reorder(&mut data, from, to).unwrap();
+                reorder(&mut data, to, from).unwrap();
+                assert_eq!(data, original);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reorder_composition() {
+        let mut direct: Vec<usize> = (0..16).collect();
+        reorder(&mut direct, Ordering::Natural, Ordering::Sequency).unwrap();
+
+        let mut via_dyadic: Vec<usize> = (0..16).collect();
+        reorder(&mut via_dyadic, Ordering::Natural, Ordering::Dyadic).unwrap();
+        reorder(&mut via_dyadic, Ordering::Dyadic, Ordering::Sequency).unwrap();
+
+        assert_eq!(direct, via_dyadic);
+    }
+
+    #[test]
+    fn test_sequency_counts_sign_changes() {
+        let n = 16;
+        for k in 0..n {
+            // Row `k` of the sequency-ordered Walsh matrix
+            let mut row = vec![0i32; n];
+            row[reverse_bits(gray(k), 4)] = 1;
+            crate::core::fwht_slice(&mut row).unwrap();
+
+            let changes = row.windows(2).filter(|w| w[0] != w[1]).count();
+            assert_eq!(changes, k);
+        }
+    }
+
+    #[test]
+    fn test_permute_non_power_of_two() {
+        let mut data = [1, 2, 3];
+        assert_eq!(
+            bit_reverse_permute(&mut data),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+        assert_eq!(
+            reorder(&mut data, Ordering::Natural, Ordering::Natural),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+    }
+}
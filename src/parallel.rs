@@ -0,0 +1,149 @@
+//! Multithreaded FWHT using rayon
+//!
+//! This module is available with the "rayon" feature. The transform is split
+//! in two phases:
+//!
+//! - **Early stages** (`h < PARALLEL_BLOCK`): the butterflies never cross a
+//!   block of `PARALLEL_BLOCK` elements, so every block is transformed
+//!   independently on its own thread while it stays in cache.
+//! - **Late stages** (`h >= PARALLEL_BLOCK`): each stage pairs the two halves
+//!   of every `2h` block, and those pairs are split into chunks that run in
+//!   parallel.
+//!
+//! Every element goes through the same butterflies in the same stage order as
+//! in [`fwht_slice`](crate::core::fwht_slice), so the results are
+//! bit-identical to the serial kernel.
+
+use crate::core::{butterfly_pairs, fwht_unchecked, validate_length};
+use crate::error::FwhtError;
+use rayon::prelude::*;
+use std::ops::{Add, Sub};
+
+/// Inputs shorter than this are transformed serially
+pub const PARALLEL_THRESHOLD: usize = 1 << 15;
+
+/// Block size for the early stages and chunk size for the late stages
+pub const PARALLEL_BLOCK: usize = 1 << 13;
+
+/// Parallel FWHT on a mutable slice
+///
+/// Produces exactly the same result as [`fwht_slice`](crate::core::fwht_slice).
+/// Inputs shorter than [`PARALLEL_THRESHOLD`] are transformed serially.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::parallel::fwht_slice_par;
+///
+/// let mut data = vec![1.0; 1 << 16];
+/// fwht_slice_par(&mut data).unwrap();
+/// assert_eq!(data[0], 65536.0);
+/// assert!(data[1..].iter().all(|&x| x == 0.0));
+/// ```
+pub fn fwht_slice_par<T>(data: &mut [T]) -> Result<(), FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Send + Sync,
+{
+    validate_length(data.len())?;
+    fwht_par_unchecked(data);
+    Ok(())
+}
+
+/// Runs the parallel transform on a slice whose length is already validated
+pub(crate) fn fwht_par_unchecked<T>(data: &mut [T])
+where
+    T: Add<Output = T> + Sub<Output = T> + Copy + Send + Sync,
+{
+    let n = data.len();
+    if n < PARALLEL_THRESHOLD {
+        fwht_unchecked(data);
+        return;
+    }
+
+    data.par_chunks_mut(PARALLEL_BLOCK)
+        .for_each(|block| fwht_unchecked(block));
+
+    let mut h = PARALLEL_BLOCK;
+    while h < n {
+        data.par_chunks_mut(2 * h).for_each(|block| {
+            let (lo, hi) = block.split_at_mut(h);
+            lo.par_chunks_mut(PARALLEL_BLOCK)
+                .zip(hi.par_chunks_mut(PARALLEL_BLOCK))
+                .for_each(|(lo, hi)| butterfly_pairs(lo, hi));
+        });
+        h *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::fwht_slice;
+
+    #[test]
+    fn test_par_matches_serial_below_threshold() {
+        let input: Vec<f64> = (0..1024).map(|i| (i % 17) as f64 - 8.0).collect();
+
+        let mut serial = input.clone();
+        fwht_slice(&mut serial).unwrap();
+        let mut parallel = input;
+        fwht_slice_par(&mut parallel).unwrap();
+
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn test_par_bit_identical_to_serial() {
+        for log2 in [15, 16, 18] {
+            let input: Vec<f64> = (0..1usize << log2)
+                .map(|i| ((i * 2654435761) % 1000) as f64 * 1e-3 - 0.5)
+                .collect();
+
+            let mut serial = input.clone();
+            fwht_slice(&mut serial).unwrap();
+            let mut parallel = input;
+            fwht_slice_par(&mut parallel).unwrap();
+
+            assert!(
+                serial
+                    .iter()
+                    .zip(parallel.iter())
+                    .all(|(a, b)| a.to_bits() == b.to_bits()),
+                "length 2^{}",
+                log2
+            );
+        }
+    }
+
+    #[test]
+    fn test_par_integers() {
+        let input: Vec<i64> = (0..1 << 16).map(|i| (i * 7) % 11 - 5).collect();
+
+        let mut serial = input.clone();
+        fwht_slice(&mut serial).unwrap();
+        let mut parallel = input;
+        fwht_slice_par(&mut parallel).unwrap();
+
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn test_par_errors_and_edge_cases() {
+        let mut data = vec![1.0; 3];
+        assert_eq!(
+            fwht_slice_par(&mut data),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+
+        let mut empty: Vec<f64> = vec![];
+        fwht_slice_par(&mut empty).unwrap();
+
+        let mut single = vec![5.0];
+        fwht_slice_par(&mut single).unwrap();
+        assert_eq!(single, vec![5.0]);
+    }
+}
@@ -0,0 +1,460 @@
+//! Reusable transform plans
+//!
+//! [`fwht_slice`](crate::core::fwht_slice) and friends validate the length,
+//! pick a kernel and compute the scale factor on every call. For many
+//! transforms of the same length, an [`FwhtPlan`] makes those decisions once:
+//! it validates the length, resolves the kernel, SIMD backend and threading,
+//! and computes the normalization factor. Executing the plan then only checks
+//! that the slice has the planned length.
+//!
+//! # Examples
+//!
+//! ```
+//! use fwht::plan::{FwhtPlan, PlanOptions};
+//! use fwht::{Normalization, Ordering};
+//!
+//! let options = PlanOptions {
+//!     normalization: Normalization::ByN,
+//!     ordering: Ordering::Sequency,
+//!     ..PlanOptions::default()
+//! };
+//! let plan = FwhtPlan::<f64>::new(8, options).unwrap();
+//!
+//! let mut data = [1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+//! plan.execute(&mut data).unwrap();
+//! assert_eq!(data, [0.5, 0.5, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+//!
+//! // Two transforms of 8 elements stored back to back
+//! let mut batch = [1.0; 16];
+//! plan.execute_batch(&mut batch).unwrap();
+//! assert_eq!(batch[0], 1.0);
+//! assert_eq!(batch[8], 1.0);
+//! ```
+
+use crate::core::{
+    last_stage_scaled, sequency_scaled, sequency_unchecked, stages_with_kernel, validate_length,
+    validate_multiple, Kernel,
+};
+use crate::error::FwhtError;
+use crate::normalize::{Normalization, Normalize};
+use crate::ordering::{bit_reverse_unchecked, Ordering};
+use crate::simd::{backend_kernel, Backend};
+use std::fmt;
+use std::ops::{Add, Sub};
+
+/// Options used to build an [`FwhtPlan`]
+///
+/// The default is the plain transform of [`fwht_slice`](crate::core::fwht_slice):
+/// no normalization, natural ordering, automatic kernel selection, no SIMD
+/// backend and a single thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PlanOptions {
+    /// Scaling of the forward transform
+    pub normalization: Normalization,
+    /// Ordering of the output coefficients
+    ///
+    /// [`Ordering::Sequency`] always runs the direct sequency kernel of
+    /// [`fwht_slice_sequency`](crate::core::fwht_slice_sequency), ignoring
+    /// `kernel`, `backend` and `parallel`.
+    pub ordering: Ordering,
+    /// Butterfly kernel used by the generic path
+    pub kernel: Kernel,
+    /// SIMD backend for `f32`, `f64`, `i16` and `i32`
+    ///
+    /// Ignored for other element types, which always use `kernel`.
+    pub backend: Option<Backend>,
+    /// Splits large transforms across the rayon thread pool
+    ///
+    /// Ignored unless the "rayon" feature is enabled.
+    pub parallel: bool,
+}
+
+/// How a plan runs the butterfly stages
+#[derive(Clone, Copy)]
+enum Strategy<T> {
+    /// Generic kernel, with the scale factor fused into the last stage
+    Kernel(Kernel),
+    /// SIMD kernel for one backend, scaled in a separate pass
+    Simd(Backend, fn(&mut [T], Backend)),
+    /// Multithreaded kernel, scaled in a separate pass
+    #[cfg(feature = "rayon")]
+    Parallel,
+    /// Direct sequency-ordered kernel, with the scale factor fused into the
+    /// last stage
+    Sequency,
+}
+
+/// A transform of a fixed length with every decision made up front
+///
+/// Build a plan once with [`FwhtPlan::new`] and reuse it with
+/// [`FwhtPlan::execute`] or [`FwhtPlan::execute_batch`]. Plans are immutable,
+/// so one plan can be shared between threads.
+pub struct FwhtPlan<T: Normalize> {
+    len: usize,
+    options: PlanOptions,
+    strategy: Strategy<T>,
+    factor: Option<T::Factor>,
+}
+
+impl<T> FwhtPlan<T>
+where
+    T: Add<Output = T> + Sub<Output = T> + Normalize + Send + Sync + 'static,
+{
+    /// Plans a transform of `n` elements
+    ///
+    /// # Errors
+    ///
+    /// - [`FwhtError::NotPowerOfTwo`] if `n` is not a power of 2
+    /// - [`FwhtError::UnsupportedNormalization`] if the normalization cannot
+    ///   be represented in `T`
+    /// - [`FwhtError::UnsupportedBackend`] if the CPU does not support the
+    ///   requested backend
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::plan::{FwhtPlan, PlanOptions};
+    /// use fwht::{FwhtError, Normalization};
+    ///
+    /// assert!(FwhtPlan::<f32>::new(1024, PlanOptions::default()).is_ok());
+    ///
+    /// let options = PlanOptions {
+    ///     normalization: Normalization::Orthonormal,
+    ///     ..PlanOptions::default()
+    /// };
+    /// assert_eq!(
+    ///     FwhtPlan::<i32>::new(8, options).err(),
+    ///     Some(FwhtError::UnsupportedNormalization { len: 8 })
+    /// );
+    /// ```
+    pub fn new(n: usize, options: PlanOptions) -> Result<Self, FwhtError> {
+        validate_length(n)?;
+        let factor = options.normalization.factor::<T>(n)?;
+
+        if let Some(backend) = options.backend {
+            if !backend.is_supported() {
+                return Err(FwhtError::UnsupportedBackend { backend });
+            }
+        }
+
+        let strategy = match (options.backend, backend_kernel::<T>()) {
+            _ if options.ordering == Ordering::Sequency => Strategy::Sequency,
+            #[cfg(feature = "rayon")]
+            _ if options.parallel && n >= crate::parallel::PARALLEL_THRESHOLD => Strategy::Parallel,
+            (Some(backend), Some(kernel)) => Strategy::Simd(backend, kernel),
+            _ => Strategy::Kernel(options.kernel.resolve::<T>(n)),
+        };
+
+        Ok(FwhtPlan {
+            len: n,
+            options,
+            strategy,
+            factor,
+        })
+    }
+
+    /// Transforms `data` in place
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::LengthMismatch`] if `data` does not have the
+    /// planned length.
+    pub fn execute(&self, data: &mut [T]) -> Result<(), FwhtError> {
+        if data.len() != self.len {
+            return Err(FwhtError::LengthMismatch {
+                expected: self.len,
+                got: data.len(),
+            });
+        }
+
+        self.execute_unchecked(data);
+        Ok(())
+    }
+
+    /// Transforms every consecutive run of `len()` elements of `data` in place
+    ///
+    /// `data` holds the vectors back to back (row-major `batch × len()`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::LengthMismatch`] if `data.len()` is not a
+    /// multiple of the planned length, with the next multiple as `expected`.
+    pub fn execute_batch(&self, data: &mut [T]) -> Result<(), FwhtError> {
+        validate_multiple(data.len(), self.len)?;
+        if self.len == 0 {
+            return Ok(());
+        }
+
+        for row in data.chunks_exact_mut(self.len) {
+            self.execute_unchecked(row);
+        }
+        Ok(())
+    }
+
+    fn execute_unchecked(&self, data: &mut [T]) {
+        match (self.strategy, self.factor) {
+            (Strategy::Kernel(kernel), Some(factor)) => {
+                stages_with_kernel(data, 1, self.len / 2, kernel);
+                last_stage_scaled(data, factor);
+            }
+            (Strategy::Kernel(kernel), None) => stages_with_kernel(data, 1, self.len, kernel),
+            (Strategy::Simd(backend, kernel), factor) => {
+                kernel(data, backend);
+                scale(data, factor);
+            }
+            #[cfg(feature = "rayon")]
+            (Strategy::Parallel, factor) => {
+                crate::parallel::fwht_par_unchecked(data);
+                scale(data, factor);
+            }
+            (Strategy::Sequency, Some(factor)) => sequency_scaled(data, factor),
+            (Strategy::Sequency, None) => sequency_unchecked(data),
+        }
+
+        if self.options.ordering == Ordering::Dyadic {
+            bit_reverse_unchecked(data);
+        }
+    }
+}
+
+impl<T: Normalize> FwhtPlan<T> {
+    /// Length of the planned transform
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the plan transforms empty slices
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Options the plan was built with
+    pub fn options(&self) -> PlanOptions {
+        self.options
+    }
+
+    /// Generic kernel the plan runs, or `None` if it uses SIMD, threads or
+    /// the sequency kernel
+    pub fn kernel(&self) -> Option<Kernel> {
+        match self.strategy {
+            Strategy::Kernel(kernel) => Some(kernel),
+            _ => None,
+        }
+    }
+
+    /// SIMD backend the plan runs, if any
+    pub fn backend(&self) -> Option<Backend> {
+        match self.strategy {
+            Strategy::Simd(backend, _) => Some(backend),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Normalize> fmt::Debug for FwhtPlan<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FwhtPlan")
+            .field("len", &self.len)
+            .field("options", &self.options)
+            .field("kernel", &self.kernel())
+            .field("backend", &self.backend())
+            .finish()
+    }
+}
+
+/// Applies `factor` to every element, if any
+fn scale<T: Normalize>(data: &mut [T], factor: Option<T::Factor>) {
+    if let Some(factor) = factor {
+        for x in data.iter_mut() {
+            *x = x.scale(factor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{fwht_slice_normalized, fwht_slice_ordered};
+
+    fn input(n: usize) -> Vec<f64> {
+        (0..n).map(|i| ((i * 37) % 11) as f64 - 5.0).collect()
+    }
+
+    #[test]
+    fn test_plan_matches_slice_functions() {
+        let normalizations = [
+            Normalization::None,
+            Normalization::Orthonormal,
+            Normalization::ByN,
+        ];
+        let orderings = [Ordering::Natural, Ordering::Sequency, Ordering::Dyadic];
+
+        for log2 in 0..10 {
+            let n = 1 << log2;
+            for &normalization in &normalizations {
+                for &ordering in &orderings {
+                    let options = PlanOptions {
+                        normalization,
+                        ordering,
+                        ..PlanOptions::default()
+                    };
+                    let plan = FwhtPlan::new(n, options).unwrap();
+
+                    let mut expected = input(n);
+                    fwht_slice_normalized(&mut expected, normalization).unwrap();
+                    crate::ordering::reorder(&mut expected, Ordering::Natural, ordering).unwrap();
+
+                    let mut data = input(n);
+                    plan.execute(&mut data).unwrap();
+                    assert_eq!(
+                        data, expected,
+                        "{:?} {:?} n = {}",
+                        normalization, ordering, n
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_plan_every_kernel_and_backend() {
+        let n = 1 << 11;
+        let mut expected = input(n);
+        fwht_slice_ordered(&mut expected, Ordering::Dyadic).unwrap();
+
+        let kernels = [
+            Kernel::Auto,
+            Kernel::Radix2,
+            Kernel::Radix4,
+            Kernel::Radix8,
+            Kernel::Blocked,
+        ];
+        for kernel in kernels {
+            let options = PlanOptions {
+                ordering: Ordering::Dyadic,
+                kernel,
+                ..PlanOptions::default()
+            };
+            let plan = FwhtPlan::new(n, options).unwrap();
+            assert_eq!(plan.kernel(), Some(kernel.resolve::<f64>(n)));
+
+            let mut data = input(n);
+            plan.execute(&mut data).unwrap();
+            assert_eq!(data, expected, "{:?}", kernel);
+        }
+
+        for backend in Backend::ALL.into_iter().filter(|b| b.is_supported()) {
+            let options = PlanOptions {
+                ordering: Ordering::Dyadic,
+                backend: Some(backend),
+                ..PlanOptions::default()
+            };
+            let plan = FwhtPlan::new(n, options).unwrap();
+            assert_eq!(plan.backend(), Some(backend));
+
+            let mut data = input(n);
+            plan.execute(&mut data).unwrap();
+            assert_eq!(data, expected, "{}", backend);
+        }
+    }
+
+    #[test]
+    fn test_backend_ignored_without_simd_kernel() {
+        let options = PlanOptions {
+            backend: Some(Backend::Scalar),
+            ..PlanOptions::default()
+        };
+        let plan = FwhtPlan::<i64>::new(4, options).unwrap();
+        assert_eq!(plan.backend(), None);
+
+        let mut data = [1i64, 1, 1, 0];
+        plan.execute(&mut data).unwrap();
+        assert_eq!(data, [3, 1, 1, -1]);
+    }
+
+    #[test]
+    fn test_execute_batch() {
+        let n = 16;
+        let plan = FwhtPlan::<f64>::new(n, PlanOptions::default()).unwrap();
+
+        let mut batch: Vec<f64> = (0..5).flat_map(|_| input(n)).collect();
+        plan.execute_batch(&mut batch).unwrap();
+
+        let mut expected = input(n);
+        plan.execute(&mut expected).unwrap();
+        for row in batch.chunks_exact(n) {
+            assert_eq!(row, &expected[..]);
+        }
+
+        plan.execute_batch(&mut []).unwrap();
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        assert_eq!(
+            FwhtPlan::<f64>::new(12, PlanOptions::default()).err(),
+            Some(FwhtError::NotPowerOfTwo { len: 12 })
+        );
+
+        let plan = FwhtPlan::<f64>::new(8, PlanOptions::default()).unwrap();
+        assert_eq!(
+            plan.execute(&mut [0.0; 4]),
+            Err(FwhtError::LengthMismatch {
+                expected: 8,
+                got: 4
+            })
+        );
+        assert_eq!(
+            plan.execute_batch(&mut [0.0; 12]),
+            Err(FwhtError::LengthMismatch {
+                expected: 16,
+                got: 12
+            })
+        );
+
+        let empty = FwhtPlan::<f64>::new(0, PlanOptions::default()).unwrap();
+        assert!(empty.is_empty());
+        empty.execute(&mut []).unwrap();
+        assert_eq!(
+            empty.execute_batch(&mut [0.0]),
+            Err(FwhtError::LengthMismatch {
+                expected: 0,
+                got: 1
+            })
+        );
+
+        for backend in Backend::ALL.into_iter().filter(|b| !b.is_supported()) {
+            let options = PlanOptions {
+                backend: Some(backend),
+                ..PlanOptions::default()
+            };
+            assert_eq!(
+                FwhtPlan::<f32>::new(8, options).err(),
+                Some(FwhtError::UnsupportedBackend { backend })
+            );
+        }
+    }
+
+    #[test]
+    fn test_parallel_plan() {
+        let n = 1 << 17;
+        // Every field listed, so this literal must compile with and without rayon
+        let options = PlanOptions {
+            normalization: Normalization::ByN,
+            ordering: Ordering::Dyadic,
+            kernel: Kernel::Auto,
+            backend: None,
+            parallel: true,
+        };
+        let plan = FwhtPlan::new(n, options).unwrap();
+        assert_eq!(plan.kernel().is_none(), cfg!(feature = "rayon"));
+
+        let mut expected = input(n);
+        fwht_slice_normalized(&mut expected, Normalization::ByN).unwrap();
+        crate::ordering::reorder(&mut expected, Ordering::Natural, Ordering::Dyadic).unwrap();
+
+        let mut data = input(n);
+        plan.execute(&mut data).unwrap();
+        assert_eq!(data, expected);
+    }
+}
@@ -0,0 +1,591 @@
+//! Autotuning planner with exportable wisdom
+//!
+//! The fastest kernel for a given length depends on the machine: cache sizes,
+//! SIMD width and core count all shift the balance between the radix-2/4/8,
+//! blocked, SIMD and parallel kernels. A [`Planner`] in
+//! [`PlannerMode::Measure`] times every candidate for a length and element
+//! type, keeps the fastest, and remembers the choice.
+//!
+//! The remembered choices ("wisdom") can be exported to a text file and
+//! loaded at startup, so production processes build tuned plans without
+//! measuring again.
+//!
+//! # Wisdom format
+//!
+//! One header line followed by one entry per line:
+//!
+//! ```text
+//! fwht-wisdom 1
+//! f32 1024 radix8 avx2 serial
+//! f64 1048576 blocked - parallel
+//! ```
+//!
+//! Each entry is the element type name, the length, the kernel, the SIMD
+//! backend (`-` for none) and `serial` or `parallel`. Blank lines and lines
+//! starting with `#` are ignored. Without the "rayon" feature, `parallel`
+//! entries are still imported and exported unchanged, and plan with their
+//! kernel and backend on a single thread. Type names come from
+//! [`std::any::type_name`], so wisdom should be regenerated when the
+//! compiler changes.
+//!
+//! # Examples
+//!
+//! ```
+//! use fwht::plan::PlanOptions;
+//! use fwht::planner::{Planner, PlannerMode};
+//!
+//! let mut planner = Planner::new(PlannerMode::Measure);
+//! let plan = planner.plan::<f32>(256, PlanOptions::default()).unwrap();
+//!
+//! let mut data = vec![1.0f32; 256];
+//! plan.execute(&mut data).unwrap();
+//! assert_eq!(data[0], 256.0);
+//!
+//! // Reload the measured choices into a fresh planner
+//! let wisdom = planner.export_wisdom();
+//! let mut restored = Planner::new(PlannerMode::Estimate);
+//! restored.import_wisdom(&wisdom).unwrap();
+//! let tuned = restored.plan::<f32>(256, PlanOptions::default()).unwrap();
+//! assert_eq!(tuned.options(), plan.options());
+//! ```
+
+use crate::core::{validate_length, Kernel};
+use crate::error::FwhtError;
+use crate::normalize::Normalize;
+use crate::ordering::Ordering;
+use crate::plan::{FwhtPlan, PlanOptions};
+use crate::simd::{backend_kernel, Backend};
+use std::any::type_name;
+use std::collections::BTreeMap;
+use std::fs;
+use std::hint::black_box;
+use std::io;
+use std::ops::{Add, Sub};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// First line of every wisdom file
+const WISDOM_HEADER: &str = "fwht-wisdom 1";
+
+/// Number of timed runs per candidate; the fastest one is kept
+const MEASURE_REPETITIONS: usize = 5;
+
+/// Minimum number of elements transformed in each timed run
+///
+/// Small transforms are repeated within a run so that each timing covers
+/// enough work to be measurable.
+const MEASURE_ELEMENTS: usize = 1 << 16;
+
+/// How a [`Planner`] chooses kernels without wisdom
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PlannerMode {
+    /// Use the options as given, with [`Kernel::Auto`] heuristics
+    #[default]
+    Estimate,
+    /// Time every candidate kernel and keep the fastest
+    Measure,
+}
+
+/// Kernel, backend and threading chosen for one type and length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Choice {
+    kernel: Kernel,
+    backend: Option<Backend>,
+    parallel: bool,
+}
+
+impl Choice {
+    /// Returns `options` with this choice's kernel, backend and threading
+    fn apply(self, options: PlanOptions) -> PlanOptions {
+        PlanOptions {
+            kernel: self.kernel,
+            backend: self.backend,
+            parallel: self.parallel,
+            ..options
+        }
+    }
+}
+
+/// Builds [`FwhtPlan`]s, measuring and remembering the fastest kernels
+///
+/// Choices are keyed by element type and length, and apply to any
+/// normalization and ordering.
+#[derive(Debug, Clone, Default)]
+pub struct Planner {
+    mode: PlannerMode,
+    wisdom: BTreeMap<(String, usize), Choice>,
+}
+
+impl Planner {
+    /// Creates a planner with no wisdom
+    pub fn new(mode: PlannerMode) -> Self {
+        Planner {
+            mode,
+            wisdom: BTreeMap::new(),
+        }
+    }
+
+    /// Planning mode used for lengths without wisdom
+    pub fn mode(&self) -> PlannerMode {
+        self.mode
+    }
+
+    /// Number of remembered choices
+    pub fn wisdom_len(&self) -> usize {
+        self.wisdom.len()
+    }
+
+    /// Forgets every remembered choice
+    pub fn forget_wisdom(&mut self) {
+        self.wisdom.clear();
+    }
+
+    /// Plans a transform of `n` elements of type `T`
+    ///
+    /// Remembered wisdom for `T` and `n` overrides the kernel, backend and
+    /// threading in `options`, unless it names a backend the CPU does not
+    /// support. Without wisdom, [`PlannerMode::Measure`] times
+    /// every candidate on zeroed data and remembers the fastest, while
+    /// [`PlannerMode::Estimate`] uses `options` unchanged. Candidates are
+    /// timed in natural order whatever the requested ordering, so the
+    /// remembered choice also suits plans with other orderings.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`FwhtPlan::new`].
+    pub fn plan<T>(&mut self, n: usize, options: PlanOptions) -> Result<FwhtPlan<T>, FwhtError>
+    where
+        T: Add<Output = T> + Sub<Output = T> + Normalize + Default + Send + Sync + 'static,
+    {
+        validate_length(n)?;
+        let key = (type_name::<T>().to_string(), n);
+
+        // Wisdom from a machine with wider SIMD is skipped rather than failing
+        let remembered = self
+            .wisdom
+            .get(&key)
+            .filter(|choice| choice.backend.is_none_or(Backend::is_supported));
+        if let Some(choice) = remembered {
+            return FwhtPlan::new(n, choice.apply(options));
+        }
+        if self.mode == PlannerMode::Estimate || n <= 1 {
+            return FwhtPlan::new(n, options);
+        }
+
+        let mut data = vec![T::default(); n.max(MEASURE_ELEMENTS)];
+        let mut best: Option<(Duration, Choice)> = None;
+        for choice in candidates::<T>(n) {
+            let plan = timing_plan(n, choice, options)?;
+            let time = measure(&plan, &mut data);
+            if best.is_none_or(|(fastest, _)| time < fastest) {
+                best = Some((time, choice));
+            }
+        }
+
+        // There is always at least one candidate
+        let (_, choice) = best.expect("no candidate kernels");
+        self.wisdom.insert(key, choice);
+        FwhtPlan::new(n, choice.apply(options))
+    }
+
+    /// Serializes the remembered choices in the wisdom format
+    pub fn export_wisdom(&self) -> String {
+        let mut text = String::from(WISDOM_HEADER);
+        text.push('\n');
+
+        for ((type_name, n), choice) in &self.wisdom {
+            let backend = choice.backend.map_or("-".to_string(), |b| b.to_string());
+            let threading = if choice.parallel {
+                "parallel"
+            } else {
+                "serial"
+            };
+            text.push_str(&format!(
+                "{} {} {} {} {}\n",
+                type_name,
+                n,
+                kernel_name(choice.kernel),
+                backend,
+                threading
+            ));
+        }
+
+        text
+    }
+
+    /// Adds the choices in `wisdom` to the planner
+    ///
+    /// Entries replace existing choices for the same type and length. If any
+    /// line is malformed, the planner is left unchanged. `parallel` entries
+    /// are kept even without the "rayon" feature, and fall back to their
+    /// kernel and backend run serially.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::InvalidWisdom`] with the line number of the first
+    /// malformed line, including a missing header or a length that is not a
+    /// power of 2.
+    pub fn import_wisdom(&mut self, wisdom: &str) -> Result<(), FwhtError> {
+        let mut lines = wisdom.lines().enumerate();
+        match lines.next() {
+            Some((_, header)) if header.trim() == WISDOM_HEADER => {}
+            _ => return Err(FwhtError::InvalidWisdom { line: 1 }),
+        }
+
+        let mut entries = Vec::new();
+        for (index, line) in lines {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let entry = parse_entry(line).ok_or(FwhtError::InvalidWisdom { line: index + 1 })?;
+            entries.push(entry);
+        }
+
+        self.wisdom.extend(entries);
+        Ok(())
+    }
+
+    /// Writes the remembered choices to a wisdom file
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error from writing the file.
+    pub fn save_wisdom<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.export_wisdom())
+    }
+
+    /// Reads a wisdom file and adds its choices to the planner
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O error from reading the file, or an error of kind
+    /// [`io::ErrorKind::InvalidData`] wrapping [`FwhtError::InvalidWisdom`]
+    /// if the file is malformed.
+    pub fn load_wisdom<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let wisdom = fs::read_to_string(path)?;
+        self.import_wisdom(&wisdom)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+/// Every kernel, backend and threading combination worth timing for `T`
+fn candidates<T: 'static>(n: usize) -> Vec<Choice> {
+    let serial = |kernel, backend| Choice {
+        kernel,
+        backend,
+        parallel: false,
+    };
+
+    let mut candidates: Vec<Choice> = [
+        Kernel::Radix2,
+        Kernel::Radix4,
+        Kernel::Radix8,
+        Kernel::Blocked,
+    ]
+    .into_iter()
+    .map(|kernel| serial(kernel, None))
+    .collect();
+
+    if backend_kernel::<T>().is_some() {
+        candidates.extend(
+            Backend::ALL
+                .into_iter()
+                .filter(|backend| backend.is_supported())
+                .map(|backend| serial(Kernel::Auto, Some(backend))),
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    if n >= crate::parallel::PARALLEL_THRESHOLD {
+        candidates.push(Choice {
+            kernel: Kernel::Auto,
+            backend: None,
+            parallel: true,
+        });
+    }
+    #[cfg(not(feature = "rayon"))]
+    let _ = n;
+
+    candidates
+}
+
+/// Plan that times `choice` for a transform planned with `options`
+///
+/// The sequency kernel runs the same way for every choice, so candidates are
+/// always timed in natural order; only the normalization is kept.
+fn timing_plan<T>(n: usize, choice: Choice, options: PlanOptions) -> Result<FwhtPlan<T>, FwhtError>
+where
+    T: Add<Output = T> + Sub<Output = T> + Normalize + Send + Sync + 'static,
+{
+    let options = PlanOptions {
+        ordering: Ordering::Natural,
+        ..choice.apply(options)
+    };
+    FwhtPlan::new(n, options)
+}
+
+/// Best time of [`MEASURE_REPETITIONS`] runs of `plan` over `data`
+fn measure<T>(plan: &FwhtPlan<T>, data: &mut [T]) -> Duration
+where
+    T: Add<Output = T> + Sub<Output = T> + Normalize + Send + Sync + 'static,
+{
+    // Warm up caches and, for parallel plans, the thread pool
+    let _ = plan.execute_batch(black_box(&mut *data));
+
+    (0..MEASURE_REPETITIONS)
+        .map(|_| {
+            let start = Instant::now();
+            let _ = plan.execute_batch(black_box(&mut *data));
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+fn kernel_name(kernel: Kernel) -> &'static str {
+    match kernel {
+        Kernel::Auto => "auto",
+        Kernel::Radix2 => "radix2",
+        Kernel::Radix4 => "radix4",
+        Kernel::Radix8 => "radix8",
+        Kernel::Blocked => "blocked",
+    }
+}
+
+fn parse_kernel(name: &str) -> Option<Kernel> {
+    match name {
+        "auto" => Some(Kernel::Auto),
+        "radix2" => Some(Kernel::Radix2),
+        "radix4" => Some(Kernel::Radix4),
+        "radix8" => Some(Kernel::Radix8),
+        "blocked" => Some(Kernel::Blocked),
+        _ => None,
+    }
+}
+
+fn parse_backend(name: &str) -> Option<Option<Backend>> {
+    if name == "-" {
+        return Some(None);
+    }
+    Backend::ALL
+        .into_iter()
+        .find(|backend| backend.to_string() == name)
+        .map(Some)
+}
+
+/// Parses `<type> <len> <kernel> <backend> <serial|parallel>`
+fn parse_entry(line: &str) -> Option<((String, usize), Choice)> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [type_name, n, kernel, backend, threading] = fields[..] else {
+        return None;
+    };
+
+    let n: usize = n.parse().ok()?;
+    validate_length(n).ok()?;
+    let parallel = match threading {
+        "serial" => false,
+        "parallel" => true,
+        _ => return None,
+    };
+
+    let choice = Choice {
+        kernel: parse_kernel(kernel)?,
+        backend: parse_backend(backend)?,
+        parallel,
+    };
+    Some(((type_name.to_string(), n), choice))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::fwht_slice;
+    use crate::normalize::Normalization;
+
+    #[test]
+    fn test_measure_records_wisdom() {
+        let mut planner = Planner::new(PlannerMode::Measure);
+        let plan = planner.plan::<f64>(64, PlanOptions::default()).unwrap();
+        assert_eq!(planner.wisdom_len(), 1);
+
+        let input: Vec<f64> = (0..64).map(|i| (i % 9) as f64).collect();
+        let mut expected = input.clone();
+        fwht_slice(&mut expected).unwrap();
+        let mut data = input;
+        plan.execute(&mut data).unwrap();
+        assert_eq!(data, expected);
+
+        // A second plan reuses the choice without measuring again
+        let again = planner.plan::<f64>(64, PlanOptions::default()).unwrap();
+        assert_eq!(again.options(), plan.options());
+        assert_eq!(planner.wisdom_len(), 1);
+    }
+
+    #[test]
+    fn test_sequency_plans_time_natural_kernels() {
+        let n = 1 << 12;
+        let sequency = PlanOptions {
+            ordering: Ordering::Sequency,
+            ..PlanOptions::default()
+        };
+
+        // Every candidate is timed with its own kernel, not the sequency one
+        for choice in candidates::<f64>(n) {
+            let plan = timing_plan::<f64>(n, choice, sequency).unwrap();
+            assert_eq!(plan.options().ordering, Ordering::Natural);
+            if choice.parallel {
+                assert!(plan.options().parallel);
+            } else if choice.backend.is_some() {
+                assert_eq!(plan.backend(), choice.backend);
+            } else {
+                assert_eq!(plan.kernel(), Some(choice.kernel));
+            }
+        }
+
+        let mut planner = Planner::new(PlannerMode::Measure);
+        let first = planner.plan::<f64>(n, sequency).unwrap();
+        assert_eq!(first.options().ordering, Ordering::Sequency);
+        assert_eq!(planner.wisdom_len(), 1);
+
+        // The natural plan reuses the recorded choice and runs its kernel
+        let choice = planner.wisdom[&(type_name::<f64>().to_string(), n)];
+        let natural = planner.plan::<f64>(n, PlanOptions::default()).unwrap();
+        assert_eq!(natural.options(), choice.apply(PlanOptions::default()));
+        assert_eq!(natural.options().ordering, Ordering::Natural);
+        assert!(natural.kernel().is_some() || natural.backend().is_some() || choice.parallel);
+
+        let input: Vec<f64> = (0..n).map(|i| (i % 11) as f64).collect();
+        let mut expected = input.clone();
+        fwht_slice(&mut expected).unwrap();
+        let mut data = input;
+        natural.execute(&mut data).unwrap();
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn test_unsupported_backend_wisdom_is_skipped() {
+        for backend in Backend::ALL.into_iter().filter(|b| !b.is_supported()) {
+            let mut planner = Planner::default();
+            let wisdom = format!("fwht-wisdom 1\nf32 64 auto {} serial\n", backend);
+            planner.import_wisdom(&wisdom).unwrap();
+
+            let plan = planner.plan::<f32>(64, PlanOptions::default()).unwrap();
+            assert_eq!(plan.backend(), None);
+        }
+    }
+
+    #[test]
+    fn test_estimate_does_not_record() {
+        let mut planner = Planner::new(PlannerMode::Estimate);
+        let options = PlanOptions {
+            kernel: Kernel::Radix4,
+            ..PlanOptions::default()
+        };
+        let plan = planner.plan::<i64>(32, options).unwrap();
+        assert_eq!(plan.kernel(), Some(Kernel::Radix4));
+        assert_eq!(planner.wisdom_len(), 0);
+    }
+
+    #[test]
+    fn test_wisdom_round_trip() {
+        let mut planner = Planner::new(PlannerMode::Measure);
+        planner.plan::<f32>(16, PlanOptions::default()).unwrap();
+        planner.plan::<i32>(1024, PlanOptions::default()).unwrap();
+
+        let wisdom = planner.export_wisdom();
+        assert!(wisdom.starts_with(WISDOM_HEADER));
+
+        let mut restored = Planner::default();
+        restored.import_wisdom(&wisdom).unwrap();
+        assert_eq!(restored.wisdom, planner.wisdom);
+        assert_eq!(restored.export_wisdom(), wisdom);
+    }
+
+    #[test]
+    fn test_wisdom_overrides_options() {
+        let mut planner = Planner::new(PlannerMode::Estimate);
+        planner
+            .import_wisdom("fwht-wisdom 1\n# comment\n\ni64 256 radix2 - serial\n")
+            .unwrap();
+
+        let options = PlanOptions {
+            normalization: Normalization::ByN,
+            kernel: Kernel::Blocked,
+            ..PlanOptions::default()
+        };
+        let plan = planner.plan::<i64>(256, options).unwrap();
+        assert_eq!(plan.kernel(), Some(Kernel::Radix2));
+        assert_eq!(plan.options().normalization, Normalization::ByN);
+    }
+
+    #[test]
+    fn test_parallel_wisdom_is_kept() {
+        let n = 1 << 16;
+        let wisdom = format!("{}\nf64 {} radix4 - parallel\n", WISDOM_HEADER, n);
+        let mut planner = Planner::new(PlannerMode::Estimate);
+        planner.import_wisdom(&wisdom).unwrap();
+        assert_eq!(planner.export_wisdom(), wisdom);
+
+        let plan = planner.plan::<f64>(n, PlanOptions::default()).unwrap();
+        assert!(plan.options().parallel);
+        if cfg!(feature = "rayon") {
+            assert_eq!(plan.kernel(), None);
+        } else {
+            assert_eq!(plan.kernel(), Some(Kernel::Radix4));
+        }
+
+        let input: Vec<f64> = (0..n).map(|i| (i % 13) as f64).collect();
+        let mut expected = input.clone();
+        fwht_slice(&mut expected).unwrap();
+        let mut data = input;
+        plan.execute(&mut data).unwrap();
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn test_invalid_wisdom() {
+        let mut planner = Planner::default();
+        let cases = [
+            ("", 1),
+            ("not wisdom\n", 1),
+            ("fwht-wisdom 1\nf32 12 radix2 - serial\n", 2),
+            (
+                "fwht-wisdom 1\nf32 16 radix2 - serial\nf32 16 radix3 - serial\n",
+                3,
+            ),
+            ("fwht-wisdom 1\nf32 16 radix2 neon serial\n", 2),
+            ("fwht-wisdom 1\nf32 16 radix2 -\n", 2),
+            ("fwht-wisdom 1\nf32 16 radix2 - threads\n", 2),
+        ];
+        for (wisdom, line) in cases {
+            assert_eq!(
+                planner.import_wisdom(wisdom),
+                Err(FwhtError::InvalidWisdom { line }),
+                "{:?}",
+                wisdom
+            );
+        }
+        assert_eq!(planner.wisdom_len(), 0);
+    }
+
+    #[test]
+    fn test_save_and_load_wisdom() {
+        let path = std::env::temp_dir().join(format!("fwht-wisdom-{}.txt", std::process::id()));
+
+        let mut planner = Planner::new(PlannerMode::Measure);
+        planner.plan::<f64>(128, PlanOptions::default()).unwrap();
+        planner.save_wisdom(&path).unwrap();
+
+        let mut restored = Planner::default();
+        restored.load_wisdom(&path).unwrap();
+        assert_eq!(restored.wisdom, planner.wisdom);
+
+        fs::write(&path, "garbage").unwrap();
+        let err = restored.load_wisdom(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        fs::remove_file(&path).unwrap();
+        assert!(restored.load_wisdom(&path).is_err());
+    }
+}
@@ -0,0 +1,547 @@
+//! SIMD-accelerated FWHT kernels with runtime CPU dispatch
+//!
+//! This module provides explicit SSE2, AVX2 and AVX-512 kernels for `f32`,
+//! `f64`, `i16` and `i32` on x86-64, and for `Complex<f32>` and `Complex<f64>`
+//! with the "num-complex" feature. The best instruction set supported by
+//! the running CPU is detected at runtime; on other CPUs and architectures
+//! the kernels fall back to the generic scalar path.
+//!
+//! The butterfly stages wider than one vector run on full vector registers,
+//! cache-blocked like [`Kernel::Blocked`] and fused two at a time into
+//! radix-4 butterflies, so large inputs make as few passes over memory as the
+//! scalar default. The narrower stages stay inside each vector-sized chunk,
+//! so they run as a single scalar pass over the data before the vector stages.
+//!
+//! Every backend performs the same additions and subtractions in the same
+//! order, so results are bit-identical across backends. Integer kernels use
+//! wrapping arithmetic on every backend.
+//!
+//! # Examples
+//!
+//! ```
+//! use fwht::simd::{fwht_simd, fwht_simd_with, Backend};
+//!
+//! let mut data = vec![1.0f32, 1.0, 1.0, 0.0];
+//! fwht_simd(&mut data).unwrap();
+//! assert_eq!(data, vec![3.0, 1.0, 1.0, -1.0]);
+//!
+//! // Test every backend the machine supports
+//! for backend in Backend::ALL.into_iter().filter(|b| b.is_supported()) {
+//!     let mut data: Vec<i32> = (0..64).collect();
+//!     fwht_simd_with(&mut data, backend).unwrap();
+//!     assert_eq!(data[0], 2016);
+//! }
+//! ```
+
+#[cfg(target_arch = "x86_64")]
+mod x86;
+
+use crate::core::{stages_with_kernel, validate_length, Kernel, BLOCK_BYTES};
+use crate::error::FwhtError;
+#[cfg(feature = "num-complex")]
+use num_complex::Complex;
+use std::any::TypeId;
+use std::fmt;
+use std::mem;
+use std::num::Wrapping;
+use std::ops::{Add, Sub};
+use std::sync::atomic::{AtomicU8, Ordering as AtomicOrdering};
+
+/// Instruction set used by the SIMD kernels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Backend {
+    /// Portable scalar code, available everywhere
+    Scalar,
+    /// 128-bit SSE2 vectors
+    Sse2,
+    /// 256-bit AVX2 vectors
+    Avx2,
+    /// 512-bit AVX-512 vectors (requires AVX-512F and AVX-512BW)
+    Avx512,
+}
+
+impl Backend {
+    /// All backends, from the most portable to the widest
+    pub const ALL: [Backend; 4] = [
+        Backend::Scalar,
+        Backend::Sse2,
+        Backend::Avx2,
+        Backend::Avx512,
+    ];
+
+    /// Returns `true` if the running CPU supports this backend
+    pub fn is_supported(self) -> bool {
+        match self {
+            Backend::Scalar => true,
+            #[cfg(target_arch = "x86_64")]
+            Backend::Sse2 => is_x86_feature_detected!("sse2"),
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx512 => {
+                is_x86_feature_detected!("avx512f") && is_x86_feature_detected!("avx512bw")
+            }
+            #[cfg(not(target_arch = "x86_64"))]
+            _ => false,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        self as u8 + 1
+    }
+
+    fn from_u8(value: u8) -> Option<Backend> {
+        Backend::ALL
+            .get(usize::from(value).checked_sub(1)?)
+            .copied()
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Backend::Scalar => "scalar",
+            Backend::Sse2 => "sse2",
+            Backend::Avx2 => "avx2",
+            Backend::Avx512 => "avx512",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Backend forced by [`force_backend`], or 0 when detection is used
+static FORCED_BACKEND: AtomicU8 = AtomicU8::new(0);
+
+/// Returns the widest backend supported by the running CPU
+pub fn detected_backend() -> Backend {
+    Backend::ALL
+        .into_iter()
+        .rev()
+        .find(|backend| backend.is_supported())
+        .unwrap_or(Backend::Scalar)
+}
+
+/// Returns the backend used by [`fwht_simd`]
+///
+/// This is the backend set with [`force_backend`] if any, otherwise
+/// [`detected_backend`].
+pub fn selected_backend() -> Backend {
+    Backend::from_u8(FORCED_BACKEND.load(AtomicOrdering::Relaxed)).unwrap_or_else(detected_backend)
+}
+
+/// Forces [`fwht_simd`] to use a specific backend process-wide
+///
+/// Pass `None` to return to runtime detection.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::UnsupportedBackend`] if the running CPU does not
+/// support the requested backend.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::simd::{force_backend, selected_backend, Backend};
+///
+/// force_backend(Some(Backend::Scalar)).unwrap();
+/// assert_eq!(selected_backend(), Backend::Scalar);
+/// force_backend(None).unwrap();
+/// ```
+pub fn force_backend(backend: Option<Backend>) -> Result<(), FwhtError> {
+    let value = match backend {
+        Some(backend) if !backend.is_supported() => {
+            return Err(FwhtError::UnsupportedBackend { backend })
+        }
+        Some(backend) => backend.to_u8(),
+        None => 0,
+    };
+
+    FORCED_BACKEND.store(value, AtomicOrdering::Relaxed);
+    Ok(())
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Element types with dedicated SIMD kernels
+///
+/// Implemented for `f32`, `f64`, `i16` and `i32`, and for `Complex<f32>` and
+/// `Complex<f64>` with the "num-complex" feature. This trait is sealed.
+pub trait SimdElement: Add<Output = Self> + Sub<Output = Self> + Copy + sealed::Sealed {
+    /// Runs the full transform with `backend` on a validated slice
+    #[doc(hidden)]
+    fn fwht_backend(data: &mut [Self], backend: Backend);
+
+    /// Runs the stages with half-width below `until` with portable scalar code
+    #[doc(hidden)]
+    fn scalar_stages(data: &mut [Self], until: usize);
+}
+
+/// Runs the butterfly stages narrower than `lanes` with scalar code
+///
+/// These stages never cross a `lanes` boundary, so they run one
+/// cache-resident tile at a time, in a single pass over the data before the
+/// vector stages.
+#[cfg_attr(not(target_arch = "x86_64"), allow(dead_code))]
+fn fwht_chunks<T: SimdElement>(data: &mut [T], lanes: usize) {
+    let size = mem::size_of::<T>().max(1);
+    let tile = (1 << (BLOCK_BYTES / size).max(1).ilog2()).max(lanes);
+    for chunk in data.chunks_mut(tile) {
+        T::scalar_stages(chunk, lanes);
+    }
+}
+
+macro_rules! impl_simd_float {
+    ($($t:ident),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl SimdElement for $t {
+                fn fwht_backend(data: &mut [$t], backend: Backend) {
+                    #[cfg(target_arch = "x86_64")]
+                    if <$t as x86::X86Kernel>::fwht_x86(data, backend) {
+                        return;
+                    }
+                    let _ = backend;
+                    Self::scalar_stages(data, data.len());
+                }
+
+                fn scalar_stages(data: &mut [$t], until: usize) {
+                    stages_with_kernel(data, 1, until, Kernel::Auto);
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_simd_int {
+    ($($t:ident),*) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl SimdElement for $t {
+                fn fwht_backend(data: &mut [$t], backend: Backend) {
+                    #[cfg(target_arch = "x86_64")]
+                    if <$t as x86::X86Kernel>::fwht_x86(data, backend) {
+                        return;
+                    }
+                    let _ = backend;
+                    Self::scalar_stages(data, data.len());
+                }
+
+                fn scalar_stages(data: &mut [$t], until: usize) {
+                    // SAFETY: `Wrapping<T>` is `repr(transparent)` over `T`
+                    let wrapping = unsafe {
+                        &mut *(data as *mut [$t] as *mut [Wrapping<$t>])
+                    };
+                    stages_with_kernel(wrapping, 1, until, Kernel::Auto);
+                }
+            }
+        )*
+    };
+}
+
+/// Complex kernels run on the interleaved `[re, im, re, im, ...]` floats
+///
+/// The stages narrower than one vector run on complex chunks; the wider
+/// stages are exactly the real vector stages on the reinterpreted slice.
+#[cfg(feature = "num-complex")]
+macro_rules! impl_simd_complex {
+    ($($t:ident),*) => {
+        $(
+            impl sealed::Sealed for Complex<$t> {}
+
+            impl SimdElement for Complex<$t> {
+                fn fwht_backend(data: &mut [Complex<$t>], backend: Backend) {
+                    #[cfg(target_arch = "x86_64")]
+                    if <$t as x86::X86ComplexKernel>::fwht_complex_x86(data, backend) {
+                        return;
+                    }
+                    let _ = backend;
+                    Self::scalar_stages(data, data.len());
+                }
+
+                fn scalar_stages(data: &mut [Complex<$t>], until: usize) {
+                    stages_with_kernel(data, 1, until, Kernel::Auto);
+                }
+            }
+        )*
+    };
+}
+
+impl_simd_float!(f32, f64);
+impl_simd_int!(i16, i32);
+#[cfg(feature = "num-complex")]
+impl_simd_complex!(f32, f64);
+
+/// Returns the SIMD entry point for `T` if it is a [`SimdElement`]
+///
+/// This lets code that is generic over any element type pick the SIMD
+/// kernels when they exist, without requiring a `SimdElement` bound.
+pub(crate) fn backend_kernel<T: 'static>() -> Option<fn(&mut [T], Backend)> {
+    fn cast<S: SimdElement + 'static, T: 'static>() -> Option<fn(&mut [T], Backend)> {
+        if TypeId::of::<S>() != TypeId::of::<T>() {
+            return None;
+        }
+        let kernel: fn(&mut [S], Backend) = S::fwht_backend;
+        // SAFETY: `S` and `T` are the same type, so the function pointer
+        // types are identical
+        Some(unsafe { mem::transmute::<fn(&mut [S], Backend), fn(&mut [T], Backend)>(kernel) })
+    }
+
+    #[cfg(feature = "num-complex")]
+    let complex = || cast::<Complex<f32>, T>().or_else(cast::<Complex<f64>, T>);
+    #[cfg(not(feature = "num-complex"))]
+    let complex = || None;
+
+    cast::<f32, T>()
+        .or_else(cast::<f64, T>)
+        .or_else(cast::<i16, T>)
+        .or_else(cast::<i32, T>)
+        .or_else(complex)
+}
+
+/// FWHT on a mutable slice using the selected SIMD backend
+///
+/// Uses [`selected_backend`]: the backend forced with [`force_backend`], or
+/// the widest one the CPU supports.
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of 2.
+///
+/// # Examples
+///
+/// ```
+/// use fwht::simd::fwht_simd;
+///
+/// let mut data = [1.0f64, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+/// fwht_simd(&mut data).unwrap();
+/// assert_eq!(data, [4.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0, 0.0]);
+/// ```
+pub fn fwht_simd<T: SimdElement>(data: &mut [T]) -> Result<(), FwhtError> {
+    validate_length(data.len())?;
+    T::fwht_backend(data, selected_backend());
+    Ok(())
+}
+
+/// FWHT on a mutable slice using an explicit SIMD backend
+///
+/// # Errors
+///
+/// Returns [`FwhtError::NotPowerOfTwo`] if the input length is not a power of 2,
+/// and [`FwhtError::UnsupportedBackend`] if the CPU does not support `backend`.
+pub fn fwht_simd_with<T: SimdElement>(data: &mut [T], backend: Backend) -> Result<(), FwhtError> {
+    if !backend.is_supported() {
+        return Err(FwhtError::UnsupportedBackend { backend });
+    }
+    validate_length(data.len())?;
+    T::fwht_backend(data, backend);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{fwht_slice, fwht_slice_with_kernel, Kernel};
+
+    fn supported() -> impl Iterator<Item = Backend> {
+        Backend::ALL.into_iter().filter(|b| b.is_supported())
+    }
+
+    #[test]
+    fn test_scalar_always_supported() {
+        assert!(Backend::Scalar.is_supported());
+        assert!(detected_backend().is_supported());
+    }
+
+    #[test]
+    fn test_backend_round_trip_encoding() {
+        for backend in Backend::ALL {
+            assert_eq!(Backend::from_u8(backend.to_u8()), Some(backend));
+        }
+        assert_eq!(Backend::from_u8(0), None);
+    }
+
+    #[test]
+    fn test_f32_matches_generic_all_backends() {
+        for log2 in 0..12 {
+            let input: Vec<f32> = (0..1 << log2).map(|i| (i % 13) as f32 - 6.5).collect();
+            let mut expected = input.clone();
+            fwht_slice(&mut expected).unwrap();
+
+            for backend in supported() {
+                let mut data = input.clone();
+                fwht_simd_with(&mut data, backend).unwrap();
+                assert_eq!(data, expected, "backend {} length {}", backend, 1 << log2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_f64_matches_generic_all_backends() {
+        for log2 in 0..12 {
+            let input: Vec<f64> = (0..1 << log2).map(|i| (i % 7) as f64 * 0.25).collect();
+            let mut expected = input.clone();
+            fwht_slice(&mut expected).unwrap();
+
+            for backend in supported() {
+                let mut data = input.clone();
+                fwht_simd_with(&mut data, backend).unwrap();
+                assert_eq!(data, expected, "backend {} length {}", backend, 1 << log2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_i32_matches_generic_all_backends() {
+        for log2 in 0..12 {
+            let input: Vec<i32> = (0..1 << log2).map(|i| (i * 31) % 17 - 8).collect();
+            let mut expected = input.clone();
+            fwht_slice(&mut expected).unwrap();
+
+            for backend in supported() {
+                let mut data = input.clone();
+                fwht_simd_with(&mut data, backend).unwrap();
+                assert_eq!(data, expected, "backend {} length {}", backend, 1 << log2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_i16_matches_generic_all_backends() {
+        for log2 in 0..12 {
+            let input: Vec<i16> = (0..1 << log2).map(|i| (i % 5) as i16 - 2).collect();
+            let mut expected = input.clone();
+            fwht_slice(&mut expected).unwrap();
+
+            for backend in supported() {
+                let mut data = input.clone();
+                fwht_simd_with(&mut data, backend).unwrap();
+                assert_eq!(data, expected, "backend {} length {}", backend, 1 << log2);
+            }
+        }
+    }
+
+    #[cfg(feature = "num-complex")]
+    #[test]
+    fn test_complex_matches_generic_all_backends() {
+        for log2 in 0..12 {
+            let input: Vec<Complex<f32>> = (0..1 << log2)
+                .map(|i| Complex::new((i % 13) as f32 - 6.5, (i % 5) as f32 * 0.5))
+                .collect();
+            let mut expected = input.clone();
+            fwht_slice(&mut expected).unwrap();
+
+            let wide: Vec<Complex<f64>> = input
+                .iter()
+                .map(|z| Complex::new(z.re as f64, z.im as f64))
+                .collect();
+            let mut wide_expected = wide.clone();
+            fwht_slice(&mut wide_expected).unwrap();
+
+            for backend in supported() {
+                let mut data = input.clone();
+                fwht_simd_with(&mut data, backend).unwrap();
+                assert_eq!(data, expected, "backend {} length {}", backend, 1 << log2);
+
+                let mut data = wide.clone();
+                fwht_simd_with(&mut data, backend).unwrap();
+                assert_eq!(
+                    data,
+                    wide_expected,
+                    "backend {} length {}",
+                    backend,
+                    1 << log2
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_blocked_vector_stages_match_generic() {
+        // Long enough for the grouped wide stages, with odd and even counts
+        for log2 in [16, 17, 19, 20] {
+            let n = 1 << log2;
+            let input: Vec<f32> = (0..n).map(|i| (i % 13) as f32 - 6.0).collect();
+            let mut expected = input.clone();
+            fwht_slice_with_kernel(&mut expected, Kernel::Radix2).unwrap();
+
+            let narrow: Vec<i16> = (0..n).map(|i| (i % 5) as i16 - 2).collect();
+            let mut wrapping: Vec<Wrapping<i16>> = narrow.iter().map(|&x| Wrapping(x)).collect();
+            fwht_slice_with_kernel(&mut wrapping, Kernel::Radix2).unwrap();
+            let narrow_expected: Vec<i16> = wrapping.iter().map(|x| x.0).collect();
+
+            for backend in supported() {
+                let mut data = input.clone();
+                fwht_simd_with(&mut data, backend).unwrap();
+                assert_eq!(data, expected, "backend {} length {}", backend, n);
+
+                let mut data = narrow.clone();
+                fwht_simd_with(&mut data, backend).unwrap();
+                assert_eq!(data, narrow_expected, "backend {} length {}", backend, n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_integer_kernels_wrap() {
+        for backend in supported() {
+            let mut data = [i16::MAX; 64];
+            fwht_simd_with(&mut data, backend).unwrap();
+            assert_eq!(data[0], i16::MAX.wrapping_mul(64), "backend {}", backend);
+        }
+    }
+
+    #[test]
+    fn test_fwht_simd_errors() {
+        let mut data = [1.0f32, 2.0, 3.0];
+        assert_eq!(
+            fwht_simd(&mut data),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+
+        for backend in Backend::ALL.into_iter().filter(|b| !b.is_supported()) {
+            let mut data = [1.0f32, 2.0];
+            assert_eq!(
+                fwht_simd_with(&mut data, backend),
+                Err(FwhtError::UnsupportedBackend { backend })
+            );
+        }
+    }
+
+    #[test]
+    fn test_force_backend() {
+        assert!(force_backend(Some(Backend::Scalar)).is_ok());
+        assert_eq!(selected_backend(), Backend::Scalar);
+
+        let mut data = [1.0f32, 1.0, 1.0, 0.0];
+        fwht_simd(&mut data).unwrap();
+        assert_eq!(data, [3.0, 1.0, 1.0, -1.0]);
+
+        assert!(force_backend(None).is_ok());
+        assert_eq!(selected_backend(), detected_backend());
+    }
+
+    #[test]
+    fn test_backend_kernel_lookup() {
+        assert!(backend_kernel::<f32>().is_some());
+        assert!(backend_kernel::<i16>().is_some());
+        assert!(backend_kernel::<i64>().is_none());
+        assert!(backend_kernel::<Wrapping<i32>>().is_none());
+        #[cfg(feature = "num-complex")]
+        assert!(backend_kernel::<Complex<f64>>().is_some());
+
+        let kernel = backend_kernel::<f64>().unwrap();
+        let mut data = [1.0f64, 1.0, 1.0, 0.0];
+        kernel(&mut data, detected_backend());
+        assert_eq!(data, [3.0, 1.0, 1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Backend::Avx512.to_string(), "avx512");
+        assert_eq!(Backend::Scalar.to_string(), "scalar");
+    }
+}
@@ -0,0 +1,463 @@
+//! x86-64 kernels for SSE2, AVX2 and AVX-512
+//!
+//! Each kernel first runs the stages that fit inside one vector with
+//! [`fwht_chunks`], then the remaining stages on whole vectors with unaligned
+//! loads and stores, cache-blocked and fused into radix-4 butterflies.
+
+use super::{fwht_chunks, Backend, SimdElement};
+use crate::core::{BLOCK_BYTES, ROW_BYTES};
+#[cfg(feature = "num-complex")]
+use num_complex::Complex;
+use std::arch::x86_64::*;
+
+/// Per-type dispatch to the x86 kernels
+pub(super) trait X86Kernel: SimdElement {
+    /// Runs the transform with `backend` if it has a kernel for this type
+    ///
+    /// Returns `false` without touching `data` when the backend is scalar,
+    /// unsupported by the CPU, or wider than `data`.
+    fn fwht_x86(data: &mut [Self], backend: Backend) -> bool;
+}
+
+/// Per-type dispatch to the x86 kernels for complex numbers of `Self`
+#[cfg(feature = "num-complex")]
+pub(super) trait X86ComplexKernel: Sized {
+    /// Runs the complex transform with `backend` if it has a kernel for this type
+    ///
+    /// Returns `false` without touching `data` under the same conditions as
+    /// [`X86Kernel::fwht_x86`], measured in floats.
+    fn fwht_complex_x86(data: &mut [Complex<Self>], backend: Backend) -> bool;
+}
+
+/// Tile length and row width, in elements, for the blocked vector stages
+///
+/// Mirrors the geometry of [`Kernel::Blocked`](crate::core::Kernel::Blocked),
+/// with rows at least one vector wide.
+fn vector_geometry<T>(_data: &[T], lanes: usize) -> (usize, usize) {
+    let size = std::mem::size_of::<T>().max(1);
+    let block = (1 << (BLOCK_BYTES / size).max(1).ilog2()).max(2 * lanes);
+    let width = (1 << (ROW_BYTES / size).max(1).ilog2())
+        .max(lanes)
+        .min(block / 2);
+    (block, width)
+}
+
+/// Radix-2 vector butterflies on two rows of `$width` elements `$stride` apart
+macro_rules! radix2_rows {
+    ($ptr:expr, $stride:expr, $width:expr, $lanes:expr, $load:ident, $store:ident, $add:ident, $sub:ident) => {{
+        let (p, s) = ($ptr, $stride);
+        let mut j = 0;
+        while j < $width {
+            let a = $load(p.add(j) as *const _);
+            let b = $load(p.add(j + s) as *const _);
+            $store(p.add(j) as *mut _, $add(a, b));
+            $store(p.add(j + s) as *mut _, $sub(a, b));
+            j += $lanes;
+        }
+    }};
+}
+
+/// Radix-4 vector butterflies on four rows of `$width` elements `$stride`
+/// apart, fusing stages `$stride` and `2 * $stride`
+macro_rules! radix4_rows {
+    ($ptr:expr, $stride:expr, $width:expr, $lanes:expr, $load:ident, $store:ident, $add:ident, $sub:ident) => {{
+        let (p, s) = ($ptr, $stride);
+        let mut j = 0;
+        while j < $width {
+            let a = $load(p.add(j) as *const _);
+            let b = $load(p.add(j + s) as *const _);
+            let c = $load(p.add(j + 2 * s) as *const _);
+            let d = $load(p.add(j + 3 * s) as *const _);
+            let s0 = $add(a, b);
+            let d0 = $sub(a, b);
+            let s1 = $add(c, d);
+            let d1 = $sub(c, d);
+            $store(p.add(j) as *mut _, $add(s0, s1));
+            $store(p.add(j + s) as *mut _, $add(d0, d1));
+            $store(p.add(j + 2 * s) as *mut _, $sub(s0, s1));
+            $store(p.add(j + 3 * s) as *mut _, $sub(d0, d1));
+            j += $lanes;
+        }
+    }};
+}
+
+/// Butterfly stages with half-width of at least one vector
+///
+/// Follows the structure of the blocked scalar kernel: every cache-sized
+/// tile first runs all of its internal stages, fused pairwise into radix-4
+/// butterflies. The wider stages are then grouped so that each group runs on
+/// rows of `width` contiguous elements that stay in cache, again two stages
+/// per radix-4 butterfly. Every element goes through the same butterflies in
+/// the same stage order as the radix-2 kernel, so results are unchanged.
+macro_rules! vector_stages {
+    ($data:ident, $lanes:expr, $load:ident, $store:ident, $add:ident, $sub:ident) => {{
+        let n = $data.len();
+        let (block, width) = vector_geometry($data, $lanes);
+        let ptr = $data.as_mut_ptr();
+
+        let inner = n.min(block);
+        let mut tile = 0;
+        while tile < n {
+            let base = ptr.add(tile);
+            let mut h = $lanes;
+            while h * 2 < inner {
+                let mut i = 0;
+                while i < inner {
+                    radix4_rows!(base.add(i), h, h, $lanes, $load, $store, $add, $sub);
+                    i += 4 * h;
+                }
+                h *= 4;
+            }
+            if h < inner {
+                let mut i = 0;
+                while i < inner {
+                    radix2_rows!(base.add(i), h, h, $lanes, $load, $store, $add, $sub);
+                    i += 2 * h;
+                }
+            }
+            tile += inner;
+        }
+
+        let max_stages = (block / width).trailing_zeros();
+        let mut h = inner;
+        while h < n {
+            let stages = max_stages.min((n / h).trailing_zeros());
+            let rows = 1usize << stages;
+            let span = h * rows;
+
+            let mut group = 0;
+            while group < n {
+                let mut column = 0;
+                while column < h {
+                    let base = ptr.add(group + column);
+                    let mut t = 1;
+                    while t * 2 < rows {
+                        let mut first = 0;
+                        while first < rows {
+                            for row in first..first + t {
+                                radix4_rows!(
+                                    base.add(row * h),
+                                    t * h,
+                                    width,
+                                    $lanes,
+                                    $load,
+                                    $store,
+                                    $add,
+                                    $sub
+                                );
+                            }
+                            first += 4 * t;
+                        }
+                        t *= 4;
+                    }
+                    if t < rows {
+                        for row in 0..t {
+                            radix2_rows!(
+                                base.add(row * h),
+                                t * h,
+                                width,
+                                $lanes,
+                                $load,
+                                $store,
+                                $add,
+                                $sub
+                            );
+                        }
+                    }
+                    column += width;
+                }
+                group += span;
+            }
+
+            h = span;
+        }
+    }};
+}
+
+macro_rules! kernel {
+    ($name:ident, $feature:literal, $t:ty, $lanes:expr, $load:ident, $store:ident, $add:ident, $sub:ident) => {
+        /// # Safety
+        ///
+        /// The CPU must support the target feature, and `data.len()` must be
+        /// a power of 2 no smaller than the vector width.
+        #[target_feature(enable = $feature)]
+        unsafe fn $name(data: &mut [$t]) {
+            fwht_chunks(data, $lanes);
+            vector_stages!(data, $lanes, $load, $store, $add, $sub);
+        }
+    };
+}
+
+kernel!(
+    sse2_f32,
+    "sse2",
+    f32,
+    4,
+    _mm_loadu_ps,
+    _mm_storeu_ps,
+    _mm_add_ps,
+    _mm_sub_ps
+);
+kernel!(
+    sse2_f64,
+    "sse2",
+    f64,
+    2,
+    _mm_loadu_pd,
+    _mm_storeu_pd,
+    _mm_add_pd,
+    _mm_sub_pd
+);
+kernel!(
+    sse2_i16,
+    "sse2",
+    i16,
+    8,
+    _mm_loadu_si128,
+    _mm_storeu_si128,
+    _mm_add_epi16,
+    _mm_sub_epi16
+);
+kernel!(
+    sse2_i32,
+    "sse2",
+    i32,
+    4,
+    _mm_loadu_si128,
+    _mm_storeu_si128,
+    _mm_add_epi32,
+    _mm_sub_epi32
+);
+
+kernel!(
+    avx2_f32,
+    "avx2",
+    f32,
+    8,
+    _mm256_loadu_ps,
+    _mm256_storeu_ps,
+    _mm256_add_ps,
+    _mm256_sub_ps
+);
+kernel!(
+    avx2_f64,
+    "avx2",
+    f64,
+    4,
+    _mm256_loadu_pd,
+    _mm256_storeu_pd,
+    _mm256_add_pd,
+    _mm256_sub_pd
+);
+kernel!(
+    avx2_i16,
+    "avx2",
+    i16,
+    16,
+    _mm256_loadu_si256,
+    _mm256_storeu_si256,
+    _mm256_add_epi16,
+    _mm256_sub_epi16
+);
+kernel!(
+    avx2_i32,
+    "avx2",
+    i32,
+    8,
+    _mm256_loadu_si256,
+    _mm256_storeu_si256,
+    _mm256_add_epi32,
+    _mm256_sub_epi32
+);
+
+kernel!(
+    avx512_f32,
+    "avx512f",
+    f32,
+    16,
+    _mm512_loadu_ps,
+    _mm512_storeu_ps,
+    _mm512_add_ps,
+    _mm512_sub_ps
+);
+kernel!(
+    avx512_f64,
+    "avx512f",
+    f64,
+    8,
+    _mm512_loadu_pd,
+    _mm512_storeu_pd,
+    _mm512_add_pd,
+    _mm512_sub_pd
+);
+kernel!(
+    avx512_i16,
+    "avx512bw",
+    i16,
+    32,
+    _mm512_loadu_si512,
+    _mm512_storeu_si512,
+    _mm512_add_epi16,
+    _mm512_sub_epi16
+);
+kernel!(
+    avx512_i32,
+    "avx512f",
+    i32,
+    16,
+    _mm512_loadu_si512,
+    _mm512_storeu_si512,
+    _mm512_add_epi32,
+    _mm512_sub_epi32
+);
+
+macro_rules! impl_x86_kernel {
+    ($t:ty, $sse2:ident / $sse2_lanes:expr, $avx2:ident / $avx2_lanes:expr, $avx512:ident / $avx512_lanes:expr) => {
+        impl X86Kernel for $t {
+            fn fwht_x86(data: &mut [$t], backend: Backend) -> bool {
+                if !backend.is_supported() {
+                    return false;
+                }
+
+                let n = data.len();
+                // SAFETY: the backend's features were just checked, and
+                // callers pass validated power-of-2 lengths
+                unsafe {
+                    match backend {
+                        Backend::Sse2 if n >= $sse2_lanes => $sse2(data),
+                        Backend::Avx2 if n >= $avx2_lanes => $avx2(data),
+                        Backend::Avx512 if n >= $avx512_lanes => $avx512(data),
+                        _ => return false,
+                    }
+                }
+                true
+            }
+        }
+    };
+}
+
+impl_x86_kernel!(f32, sse2_f32 / 4, avx2_f32 / 8, avx512_f32 / 16);
+impl_x86_kernel!(f64, sse2_f64 / 2, avx2_f64 / 4, avx512_f64 / 8);
+impl_x86_kernel!(i16, sse2_i16 / 8, avx2_i16 / 16, avx512_i16 / 32);
+impl_x86_kernel!(i32, sse2_i32 / 4, avx2_i32 / 8, avx512_i32 / 16);
+
+/// Complex kernel on `$t` vectors of `$lanes` floats
+///
+/// One vector holds `$lanes / 2` complex numbers, so those stages run on
+/// complex chunks; a complex butterfly at half-width `h` is a pair of real
+/// butterflies at half-width `2h` on the interleaved floats.
+#[cfg(feature = "num-complex")]
+macro_rules! complex_kernel {
+    ($name:ident, $feature:literal, $t:ty, $lanes:expr, $load:ident, $store:ident, $add:ident, $sub:ident) => {
+        /// # Safety
+        ///
+        /// The CPU must support the target feature, and `data.len()` must be
+        /// a power of 2 with at least one vector of floats.
+        #[target_feature(enable = $feature)]
+        unsafe fn $name(data: &mut [Complex<$t>]) {
+            fwht_chunks(data, $lanes / 2);
+            // SAFETY: `Complex<T>` is `repr(C)` with the real part first
+            let floats =
+                std::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut $t, 2 * data.len());
+            vector_stages!(floats, $lanes, $load, $store, $add, $sub);
+        }
+    };
+}
+
+#[cfg(feature = "num-complex")]
+complex_kernel!(
+    sse2_c32,
+    "sse2",
+    f32,
+    4,
+    _mm_loadu_ps,
+    _mm_storeu_ps,
+    _mm_add_ps,
+    _mm_sub_ps
+);
+#[cfg(feature = "num-complex")]
+complex_kernel!(
+    sse2_c64,
+    "sse2",
+    f64,
+    2,
+    _mm_loadu_pd,
+    _mm_storeu_pd,
+    _mm_add_pd,
+    _mm_sub_pd
+);
+#[cfg(feature = "num-complex")]
+complex_kernel!(
+    avx2_c32,
+    "avx2",
+    f32,
+    8,
+    _mm256_loadu_ps,
+    _mm256_storeu_ps,
+    _mm256_add_ps,
+    _mm256_sub_ps
+);
+#[cfg(feature = "num-complex")]
+complex_kernel!(
+    avx2_c64,
+    "avx2",
+    f64,
+    4,
+    _mm256_loadu_pd,
+    _mm256_storeu_pd,
+    _mm256_add_pd,
+    _mm256_sub_pd
+);
+#[cfg(feature = "num-complex")]
+complex_kernel!(
+    avx512_c32,
+    "avx512f",
+    f32,
+    16,
+    _mm512_loadu_ps,
+    _mm512_storeu_ps,
+    _mm512_add_ps,
+    _mm512_sub_ps
+);
+#[cfg(feature = "num-complex")]
+complex_kernel!(
+    avx512_c64,
+    "avx512f",
+    f64,
+    8,
+    _mm512_loadu_pd,
+    _mm512_storeu_pd,
+    _mm512_add_pd,
+    _mm512_sub_pd
+);
+
+#[cfg(feature = "num-complex")]
+macro_rules! impl_x86_complex_kernel {
+    ($t:ty, $sse2:ident / $sse2_lanes:expr, $avx2:ident / $avx2_lanes:expr, $avx512:ident / $avx512_lanes:expr) => {
+        impl X86ComplexKernel for $t {
+            fn fwht_complex_x86(data: &mut [Complex<$t>], backend: Backend) -> bool {
+                if !backend.is_supported() {
+                    return false;
+                }
+
+                let floats = 2 * data.len();
+                // SAFETY: the backend's features were just checked, and
+                // callers pass validated power-of-2 lengths
+                unsafe {
+                    match backend {
+                        Backend::Sse2 if floats >= $sse2_lanes => $sse2(data),
+                        Backend::Avx2 if floats >= $avx2_lanes => $avx2(data),
+                        Backend::Avx512 if floats >= $avx512_lanes => $avx512(data),
+                        _ => return false,
+                    }
+                }
+                true
+            }
+        }
+    };
+}
+
+#[cfg(feature = "num-complex")]
+impl_x86_complex_kernel!(f32, sse2_c32 / 4, avx2_c32 / 8, avx512_c32 / 16);
+#[cfg(feature = "num-complex")]
+impl_x86_complex_kernel!(f64, sse2_c64 / 2, avx2_c64 / 4, avx512_c64 / 8);
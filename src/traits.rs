@@ -3,10 +3,24 @@
 //! This module defines the core trait that provides a uniform interface
 //! for Fast Walsh-Hadamard Transform operations across different container types.
 
+use crate::core::fwht_slice_into;
+use crate::error::FwhtError;
+use crate::normalize::{Normalization, Normalize};
+use crate::ordering::{reorder, Ordering};
+use std::ops::{Add, Sub};
+
 /// Trait for types that support Fast Walsh-Hadamard Transform operations
 ///
 /// This trait provides a uniform interface for applying FWHT to different
-/// container types like `Vec<T>`, `[T; N]`, and `ndarray::Array1<T>`.
+/// container types like `Vec<T>`, `[T; N]`, and one-dimensional ndarray
+/// arrays and views.
+///
+/// Implementors only have to provide [`FWHT::fwht_mut`]; the copying methods
+/// are available whenever the container is `Clone`. The normalized, ordered
+/// and out-of-place methods have default bodies built on [`FWHT::fwht_mut`]
+/// and the element slices of [`FWHT::as_fwht_slice`] and
+/// [`FWHT::as_fwht_slice_mut`], which containers override to expose their
+/// storage.
 ///
 /// # Examples
 ///
@@ -30,8 +44,25 @@ pub trait FWHT<T> {
     ///
     /// # Errors
     ///
-    /// Returns an error if the container length is not a power of 2.
-    fn fwht_mut(&mut self) -> Result<(), &'static str>;
+    /// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2.
+    fn fwht_mut(&mut self) -> Result<(), FwhtError>;
+
+    /// The container's elements as a slice, if it stores them contiguously
+    ///
+    /// Used by the default bodies of the normalized, ordered and
+    /// out-of-place methods. The default returns `None`.
+    fn as_fwht_slice(&self) -> Option<&[T]> {
+        None
+    }
+
+    /// The container's elements as a mutable slice, if it stores them
+    /// contiguously
+    ///
+    /// Used by the default bodies of the normalized and ordered methods. The
+    /// default returns `None`.
+    fn as_fwht_slice_mut(&mut self) -> Option<&mut [T]> {
+        None
+    }
 
     /// Apply FWHT and return a new container with the result
     ///
@@ -40,23 +71,371 @@ pub trait FWHT<T> {
     ///
     /// # Errors
     ///
-    /// Returns an error if the container length is not a power of 2.
-    fn fwht(&self) -> Result<Self, &'static str>
+    /// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2.
+    fn fwht(&self) -> Result<Self, FwhtError>
+    where
+        Self: Sized + Clone,
+    {
+        let mut result = self.clone();
+        result.fwht_mut()?;
+        Ok(result)
+    }
+
+    /// Apply FWHT in-place with the given normalization
+    ///
+    /// The containers of this crate fuse the scaling into the last butterfly
+    /// stage. The default body runs [`FWHT::fwht_mut`] and then scales the
+    /// elements of [`FWHT::as_fwht_slice_mut`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2,
+    /// and [`FwhtError::UnsupportedNormalization`] if the scale factor cannot be
+    /// represented in `T`. The default body returns [`FwhtError::NoSliceAccess`]
+    /// if scaling is needed and the container does not expose its elements.
+    fn fwht_mut_normalized(&mut self, normalization: Normalization) -> Result<(), FwhtError>
+    where
+        T: Normalize,
+    {
+        if normalization == Normalization::None {
+            return self.fwht_mut();
+        }
+        let n = self.as_fwht_slice().ok_or(FwhtError::NoSliceAccess)?.len();
+        let factor = normalization.factor::<T>(n)?;
+
+        self.fwht_mut()?;
+        if let (Some(factor), Some(data)) = (factor, self.as_fwht_slice_mut()) {
+            for x in data.iter_mut() {
+                *x = x.scale(factor);
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply FWHT with the given normalization and return a new container
+    ///
+    /// # Errors
+    ///
+    /// Same as [`FWHT::fwht_mut_normalized`].
+    fn fwht_normalized(&self, normalization: Normalization) -> Result<Self, FwhtError>
+    where
+        Self: Sized + Clone,
+        T: Normalize,
+    {
+        let mut result = self.clone();
+        result.fwht_mut_normalized(normalization)?;
+        Ok(result)
+    }
+
+    /// Apply the inverse FWHT in-place
+    ///
+    /// Exactly inverts [`FWHT::fwht_mut_normalized`] called with the same
+    /// `normalization`.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`FWHT::fwht_mut_normalized`].
+    fn ifwht_mut(&mut self, normalization: Normalization) -> Result<(), FwhtError>
+    where
+        T: Normalize,
+    {
+        self.fwht_mut_normalized(normalization.inverse())
+    }
+
+    /// Apply the inverse FWHT and return a new container with the result
+    ///
+    /// # Errors
+    ///
+    /// Same as [`FWHT::fwht_mut_normalized`].
+    fn ifwht(&self, normalization: Normalization) -> Result<Self, FwhtError>
+    where
+        Self: Sized + Clone,
+        T: Normalize,
+    {
+        self.fwht_normalized(normalization.inverse())
+    }
+
+    /// Write the FWHT of the container into `dst`, leaving `self` unchanged
+    ///
+    /// Unlike [`FWHT::fwht`], this allocates nothing, so it suits real-time
+    /// loops that reuse a preallocated output buffer. The default body copies
+    /// the elements of [`FWHT::as_fwht_slice`] into `dst` as part of the
+    /// transform.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a
+    /// power of 2, and [`FwhtError::LengthMismatch`] if `dst` has a different
+    /// length. The default body returns [`FwhtError::NoSliceAccess`] if the
+    /// container does not expose its elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fwht::FWHT;
+    ///
+    /// let data = vec![1.0, 1.0, 1.0, 0.0];
+    /// let mut out = [0.0; 4];
+    /// data.fwht_into(&mut out).unwrap();
+    /// assert_eq!(out, [3.0, 1.0, 1.0, -1.0]);
+    /// ```
+    fn fwht_into(&self, dst: &mut [T]) -> Result<(), FwhtError>
+    where
+        T: Add<Output = T> + Sub<Output = T> + Copy + 'static,
+    {
+        let src = self.as_fwht_slice().ok_or(FwhtError::NoSliceAccess)?;
+        fwht_slice_into(src, dst)
+    }
+
+    /// Apply FWHT in-place with the coefficients in the given ordering
+    ///
+    /// The default body runs [`FWHT::fwht_mut`] and then permutes the
+    /// elements of [`FWHT::as_fwht_slice_mut`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2.
+    /// The default body returns [`FwhtError::NoSliceAccess`] for orderings
+    /// other than [`Ordering::Natural`] if the container does not expose its
+    /// elements.
+    fn fwht_mut_ordered(&mut self, ordering: Ordering) -> Result<(), FwhtError> {
+        if ordering == Ordering::Natural {
+            return self.fwht_mut();
+        }
+        if self.as_fwht_slice_mut().is_none() {
+            return Err(FwhtError::NoSliceAccess);
+        }
+
+        self.fwht_mut()?;
+        match self.as_fwht_slice_mut() {
+            Some(data) => reorder(data, Ordering::Natural, ordering),
+            None => Err(FwhtError::NoSliceAccess),
+        }
+    }
+
+    /// Apply FWHT with the given ordering and return a new container
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2.
+    fn fwht_ordered(&self, ordering: Ordering) -> Result<Self, FwhtError>
+    where
+        Self: Sized + Clone,
+    {
+        let mut result = self.clone();
+        result.fwht_mut_ordered(ordering)?;
+        Ok(result)
+    }
+
+    /// Apply FWHT in-place using multiple threads
+    ///
+    /// Produces exactly the same result as [`FWHT::fwht_mut`]. Requires the
+    /// "rayon" feature.
+    ///
+    /// The default body runs [`FWHT::fwht_mut`] on the calling thread, so
+    /// implementors outside this crate keep compiling when the feature is
+    /// enabled elsewhere in the dependency graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2.
+    #[cfg(feature = "rayon")]
+    fn fwht_par_mut(&mut self) -> Result<(), FwhtError>
     where
-        Self: Sized;
+        T: Send + Sync,
+    {
+        self.fwht_mut()
+    }
+
+    /// Apply FWHT using multiple threads and return a new container
+    ///
+    /// Requires the "rayon" feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::NotPowerOfTwo`] if the container length is not a power of 2.
+    #[cfg(feature = "rayon")]
+    fn fwht_par(&self) -> Result<Self, FwhtError>
+    where
+        Self: Sized + Clone,
+        T: Send + Sync,
+    {
+        let mut result = self.clone();
+        result.fwht_par_mut()?;
+        Ok(result)
+    }
+}
+
+/// Trait for multi-dimensional arrays that support FWHT along axes
+///
+/// Transforming along an axis applies the 1-D FWHT to every lane parallel to
+/// that axis. Transforming along every axis gives the separable N-D
+/// Walsh-Hadamard transform.
+///
+/// Each transformed axis must have a power-of-2 length; other axes may have
+/// any length. All lengths are checked before any data is modified.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "ndarray")]
+/// # {
+/// use fwht::FWHTAxis;
+/// use ndarray::array;
+///
+/// let mut data = array![[1.0, 1.0], [1.0, 0.0]];
+/// data.fwht_axis_mut(1).unwrap();
+/// assert_eq!(data, array![[2.0, 0.0], [1.0, 1.0]]);
+///
+/// let full = array![[1.0, 1.0], [1.0, 0.0]].fwht_all_axes().unwrap();
+/// assert_eq!(full, array![[3.0, 1.0], [1.0, -1.0]]);
+/// # }
+/// ```
+#[cfg(feature = "ndarray")]
+pub trait FWHTAxis<T> {
+    /// Owned container returned by the copying methods
+    type Output;
+
+    /// Apply FWHT in-place along a single axis
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::InvalidAxis`] if `axis` is out of bounds, and
+    /// [`FwhtError::NotPowerOfTwo`] if the axis length is not a power of 2.
+    fn fwht_axis_mut(&mut self, axis: usize) -> Result<(), FwhtError>;
+
+    /// Apply FWHT in-place along each of the given axes in turn
+    ///
+    /// # Errors
+    ///
+    /// Same as [`FWHTAxis::fwht_axis_mut`], for any of the axes.
+    fn fwht_axes_mut(&mut self, axes: &[usize]) -> Result<(), FwhtError>;
+
+    /// Apply FWHT in-place along every axis
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FwhtError::NotPowerOfTwo`] if any axis length is not a power of 2.
+    fn fwht_all_axes_mut(&mut self) -> Result<(), FwhtError>;
+
+    /// Apply FWHT along a single axis and return a new array
+    ///
+    /// # Errors
+    ///
+    /// Same as [`FWHTAxis::fwht_axis_mut`].
+    fn fwht_axis(&self, axis: usize) -> Result<Self::Output, FwhtError>;
+
+    /// Apply FWHT along each of the given axes and return a new array
+    ///
+    /// # Errors
+    ///
+    /// Same as [`FWHTAxis::fwht_axes_mut`].
+    fn fwht_axes(&self, axes: &[usize]) -> Result<Self::Output, FwhtError>;
+
+    /// Apply FWHT along every axis and return a new array
+    ///
+    /// # Errors
+    ///
+    /// Same as [`FWHTAxis::fwht_all_axes_mut`].
+    fn fwht_all_axes(&self) -> Result<Self::Output, FwhtError>;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Implements only the required method, as a downstream crate would
+    struct Wrapper(Vec<f64>);
+
+    impl FWHT<f64> for Wrapper {
+        fn fwht_mut(&mut self) -> Result<(), FwhtError> {
+            self.0.fwht_mut()
+        }
+    }
+
+    /// Also exposes its elements, enabling every default body
+    struct SliceWrapper(Vec<f64>);
+
+    impl FWHT<f64> for SliceWrapper {
+        fn fwht_mut(&mut self) -> Result<(), FwhtError> {
+            self.0.fwht_mut()
+        }
+
+        fn as_fwht_slice(&self) -> Option<&[f64]> {
+            Some(&self.0)
+        }
+
+        fn as_fwht_slice_mut(&mut self) -> Option<&mut [f64]> {
+            Some(&mut self.0)
+        }
+    }
+
+    #[test]
+    fn test_required_methods_only() {
+        let mut data = Wrapper(vec![1.0, 1.0, 1.0, 0.0]);
+        data.fwht_mut().unwrap();
+        assert_eq!(data.0, vec![3.0, 1.0, 1.0, -1.0]);
+
+        #[cfg(feature = "rayon")]
+        {
+            data.fwht_par_mut().unwrap();
+            assert_eq!(data.0, vec![4.0, 4.0, 4.0, 0.0]);
+        }
+
+        data.fwht_mut_normalized(Normalization::None).unwrap();
+        data.fwht_mut_ordered(Ordering::Natural).unwrap();
+        assert_eq!(
+            data.fwht_mut_normalized(Normalization::ByN),
+            Err(FwhtError::NoSliceAccess)
+        );
+        assert_eq!(
+            data.fwht_mut_ordered(Ordering::Sequency),
+            Err(FwhtError::NoSliceAccess)
+        );
+        assert_eq!(data.fwht_into(&mut [0.0; 4]), Err(FwhtError::NoSliceAccess));
+    }
+
+    #[test]
+    fn test_default_bodies_match_vec() {
+        let input: Vec<f64> = (0..16).map(|i| ((i * 7) % 5) as f64).collect();
+
+        for normalization in [
+            Normalization::None,
+            Normalization::Orthonormal,
+            Normalization::ByN,
+        ] {
+            let mut data = SliceWrapper(input.clone());
+            data.fwht_mut_normalized(normalization).unwrap();
+            assert_eq!(data.0, input.fwht_normalized(normalization).unwrap());
+        }
+
+        for ordering in [Ordering::Natural, Ordering::Sequency, Ordering::Dyadic] {
+            let mut data = SliceWrapper(input.clone());
+            data.fwht_mut_ordered(ordering).unwrap();
+            assert_eq!(data.0, input.fwht_ordered(ordering).unwrap());
+        }
+
+        let mut out = [0.0; 16];
+        SliceWrapper(input.clone()).fwht_into(&mut out).unwrap();
+        assert_eq!(out.to_vec(), input.fwht().unwrap());
+
+        assert_eq!(
+            SliceWrapper(vec![1.0; 3]).fwht_mut_normalized(Normalization::ByN),
+            Err(FwhtError::NotPowerOfTwo { len: 3 })
+        );
+    }
+
     fn _assert_trait_signature() {
-        fn _test_fwht_mut<C: FWHT<f64>>(container: &mut C) -> Result<(), &'static str> {
+        fn _test_fwht_mut<C: FWHT<f64>>(container: &mut C) -> Result<(), FwhtError> {
             container.fwht_mut()
         }
 
-        fn _test_fwht<C: FWHT<f64>>(container: &C) -> Result<C, &'static str> {
+        fn _test_fwht<C: FWHT<f64> + Clone>(container: &C) -> Result<C, FwhtError> {
             container.fwht()
         }
+
+        fn _test_ifwht<C: FWHT<f64> + Clone>(container: &C) -> Result<C, FwhtError> {
+            container.ifwht(Normalization::Orthonormal)
+        }
     }
 }